@@ -73,6 +73,8 @@ func main{
     local keccak_ptr = keccak_ptr;
     local poseidon_ptr: PoseidonBuiltin* = poseidon_ptr;
     local range_check96_ptr = range_check96_ptr;
+    local add_mod_ptr = add_mod_ptr;
+    local mul_mod_ptr = mul_mod_ptr;
     local simple_bootloader_output_end: felt* = simple_bootloader_output_ptr;
     info_string('re-alloc builtins');
 
@@ -109,15 +109,13 @@ func execute_task{builtin_ptrs: BuiltinData*, self_range_check_ptr}(
 
     // Write hash_chain result to output_ptr + 1.
     assert [output_ptr + 1] = hash;
-    // TODO: implement blake2s hash validation hint in Rust
-    // %{
-    //     # Validate hash.
-    //     from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+    %{
+        # Validate hash.
+        from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
 
-    //     assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
-    //         program=task.get_program(),
-    //         use_poseidon=bool(ids.use_poseidon)), 'Computed hash does not match input.'
-    // %}
+        assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
+            program=task.get_program(), use_blake2s=True), 'Computed hash does not match input.'
+    %}
 
     // Set the program entry point, so the bootloader can later run the program.
     local builtin_list: felt* = &program_header.builtin_list;
@@ -143,8 +141,8 @@ func execute_task{builtin_ptrs: BuiltinData*, self_range_check_ptr}(
         keccak=input_builtin_ptrs.keccak,
         poseidon=input_builtin_ptrs.poseidon,
         range_check96=input_builtin_ptrs.range_check96,
-        add_mod=0,
-        mul_mod=0,
+        add_mod=input_builtin_ptrs.add_mod,
+        mul_mod=input_builtin_ptrs.mul_mod,
     );
 
     // Call select_input_builtins to get the relevant input builtin pointers for the task.
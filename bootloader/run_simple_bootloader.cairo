@@ -22,6 +22,8 @@ func run_simple_bootloader{
     keccak_ptr,
     poseidon_ptr: PoseidonBuiltin*,
     range_check96_ptr,
+    add_mod_ptr,
+    mul_mod_ptr,
 }() {
     alloc_locals;
     local task_range_check_ptr;
@@ -56,8 +58,8 @@ func run_simple_bootloader{
         keccak=keccak_ptr,
         poseidon=cast(poseidon_ptr, felt),
         range_check96=range_check96_ptr,
-        add_mod=0,
-        mul_mod=0,
+        add_mod=add_mod_ptr,
+        mul_mod=mul_mod_ptr,
     );
 
     info_string('builtin_ptrs_before');
@@ -124,6 +126,8 @@ func run_simple_bootloader{
     let keccak_ptr = builtin_ptrs.keccak;
     let poseidon_ptr = cast(builtin_ptrs.poseidon, PoseidonBuiltin*);
     let range_check96_ptr = builtin_ptrs.range_check96;
+    let add_mod_ptr = builtin_ptrs.add_mod;
+    let mul_mod_ptr = builtin_ptrs.mul_mod;
 
     // 'execute_tasks' runs untrusted code and uses the range_check builtin to verify that
     // the builtin pointers were advanced correctly by said code.
@@ -6,6 +6,7 @@ use cairo_vm::hint_processor::builtin_hint_processor::hint_utils::{
     get_integer_from_var_name, get_ptr_from_var_name, insert_value_from_var_name,
     insert_value_into_ap,
 };
+use cairo_vm::types::errors::math_errors::MathError;
 use cairo_vm::types::exec_scope::ExecutionScopes;
 use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
 use cairo_vm::vm::errors::hint_errors::HintError;
@@ -204,18 +205,32 @@ pub fn enter_packed_output_scope(
 ) -> Result<(), HintError> {
     // task_id = len(packed_outputs) - ids.n_subtasks
     let packed_outputs: Vec<PackedOutput> = exec_scopes.get(vars::PACKED_OUTPUTS)?;
-    let n_subtasks = get_integer_from_var_name(
+    let n_subtasks_felt = get_integer_from_var_name(
         "n_subtasks",
         vm,
         &hint_data.ids_data,
         &hint_data.ap_tracking,
-    )
-    .unwrap()
-    .to_usize()
-    .unwrap();
-    let task_id = packed_outputs.len() - n_subtasks;
+    )?;
+    let n_subtasks = n_subtasks_felt
+        .to_usize()
+        .ok_or(MathError::Felt252ToUsizeConversion(Box::new(
+            n_subtasks_felt,
+        )))?;
+    let packed_output = packed_outputs
+        .len()
+        .checked_sub(n_subtasks)
+        .and_then(|task_id| packed_outputs.get(task_id))
+        .ok_or_else(|| {
+            HintError::CustomHint(
+                format!(
+                    "Invalid number of remaining subtasks {n_subtasks}, out of {}",
+                    packed_outputs.len()
+                )
+                .into_boxed_str(),
+            )
+        })?;
     // packed_output: PackedOutput = packed_outputs[task_id]
-    let packed_output: Box<dyn Any> = Box::new(packed_outputs[task_id].clone());
+    let packed_output: Box<dyn Any> = Box::new(packed_output.clone());
 
     // vm_enter_scope(new_scope_locals=dict(packed_output=packed_output))
     exec_scopes.enter_scope(HashMap::from([(
@@ -964,6 +979,7 @@ mod tests {
             PackedOutput::Composite(CompositePackedOutput {
                 outputs: vec![],
                 subtasks: subtasks.clone(),
+                fact_topologies: vec![],
             }),
         );
 
@@ -1001,6 +1017,7 @@ mod tests {
             Box::new(PackedOutput::Composite(CompositePackedOutput {
                 outputs: vec![Felt252::from(42)],
                 subtasks: vec![],
+                fact_topologies: vec![],
             })),
         );
 
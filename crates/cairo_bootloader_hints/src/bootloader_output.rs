@@ -0,0 +1,295 @@
+use cairo_vm::Felt252;
+use num_traits::ToPrimitive;
+use serde::Serialize;
+
+/// Size of the `[size, program_hash]` header written before the output of each task.
+pub const TASK_OUTPUT_HEADER_SIZE: usize = 2;
+
+/// Size of the output of a Cairo verifier task: the header, the hash of the verified
+/// (simple bootloader) program and the hash of its output.
+pub const COMPOSITE_TASK_OUTPUT_SIZE: usize = TASK_OUTPUT_HEADER_SIZE + 2;
+
+#[derive(thiserror_no_std::Error, Debug, PartialEq, Eq)]
+pub enum BootloaderOutputError {
+    #[error("Bootloader output is empty")]
+    EmptyOutput,
+
+    #[error("Invalid number of tasks in the bootloader output: {0}")]
+    InvalidNumberOfTasks(Box<Felt252>),
+
+    #[error("Task {0}: invalid output size {1}")]
+    InvalidTaskSize(usize, Box<Felt252>),
+
+    #[error("Task {0}: output ends at offset {1}, after the end of the bootloader output ({2})")]
+    TaskOutputOutOfBounds(usize, usize, usize),
+
+    #[error(
+        "Task {0}: the output of a Cairo verifier task must have size {}, got {1}",
+        COMPOSITE_TASK_OUTPUT_SIZE
+    )]
+    InvalidCompositeTaskSize(usize, usize),
+
+    #[error("Bootloader output has {0} trailing words after the last task")]
+    TrailingOutput(usize),
+}
+
+/// The output of a single task, as written by the (simple) bootloader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskOutput {
+    Plain {
+        program_hash: Felt252,
+        output: Vec<Felt252>,
+    },
+    /// The output of a Cairo verifier that verified a proof of the simple bootloader. Only found
+    /// in the output of the simple bootloader, the bootloader unpacks these tasks into the plain
+    /// outputs of the verified tasks.
+    Composite {
+        cairo_verifier_program_hash: Felt252,
+        simple_bootloader_program_hash: Felt252,
+        /// Hash of the output of the verified simple bootloader run.
+        output_hash: Felt252,
+    },
+}
+
+impl TaskOutput {
+    /// Hash of the program that produced this output.
+    pub fn program_hash(&self) -> &Felt252 {
+        match self {
+            TaskOutput::Plain { program_hash, .. } => program_hash,
+            TaskOutput::Composite {
+                cairo_verifier_program_hash,
+                ..
+            } => cairo_verifier_program_hash,
+        }
+    }
+
+    /// The program output, without the task header.
+    pub fn output(&self) -> Vec<Felt252> {
+        match self {
+            TaskOutput::Plain { output, .. } => output.clone(),
+            TaskOutput::Composite {
+                simple_bootloader_program_hash,
+                output_hash,
+                ..
+            } => vec![*simple_bootloader_program_hash, *output_hash],
+        }
+    }
+
+    /// Number of words of this task in the bootloader output, header included.
+    pub fn size(&self) -> usize {
+        match self {
+            TaskOutput::Plain { output, .. } => TASK_OUTPUT_HEADER_SIZE + output.len(),
+            TaskOutput::Composite { .. } => COMPOSITE_TASK_OUTPUT_SIZE,
+        }
+    }
+}
+
+/// The decoded output of the (simple) bootloader: the number of tasks, followed by
+/// `[size, program_hash, output...]` for each task, `size` including the two header words.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BootloaderOutput {
+    pub tasks: Vec<TaskOutput>,
+}
+
+impl BootloaderOutput {
+    /// Decodes the content of the output segment of the bootloader. All the tasks are decoded as
+    /// plain tasks.
+    pub fn decode(bootloader_output: &[Felt252]) -> Result<Self, BootloaderOutputError> {
+        Self::decode_with_verifiers(bootloader_output, &[])
+    }
+
+    /// Same as `decode`, but decodes the tasks run by one of the given Cairo verifier programs
+    /// as composite tasks, as the bootloader does with
+    /// `BootloaderConfig::supported_cairo_verifier_program_hashes`.
+    pub fn decode_with_verifiers(
+        bootloader_output: &[Felt252],
+        supported_cairo_verifier_program_hashes: &[Felt252],
+    ) -> Result<Self, BootloaderOutputError> {
+        let (n_tasks, mut tasks_output) = bootloader_output
+            .split_first()
+            .ok_or(BootloaderOutputError::EmptyOutput)?;
+        let n_tasks = n_tasks
+            .to_usize()
+            .ok_or_else(|| BootloaderOutputError::InvalidNumberOfTasks(Box::new(*n_tasks)))?;
+
+        // Do not trust `n_tasks` for the allocation, every task takes at least two words.
+        let mut tasks = Vec::with_capacity(n_tasks.min(tasks_output.len() / 2));
+        let mut offset = 1;
+        for task_index in 0..n_tasks {
+            let size_felt =
+                tasks_output
+                    .first()
+                    .ok_or(BootloaderOutputError::TaskOutputOutOfBounds(
+                        task_index,
+                        offset + 1,
+                        bootloader_output.len(),
+                    ))?;
+            let size = size_felt
+                .to_usize()
+                .filter(|size| *size >= TASK_OUTPUT_HEADER_SIZE)
+                .ok_or_else(|| {
+                    BootloaderOutputError::InvalidTaskSize(task_index, Box::new(*size_felt))
+                })?;
+            if size > tasks_output.len() {
+                return Err(BootloaderOutputError::TaskOutputOutOfBounds(
+                    task_index,
+                    offset + size,
+                    bootloader_output.len(),
+                ));
+            }
+
+            let (task_output, rest) = tasks_output.split_at(size);
+            let program_hash = task_output[1];
+            let output = &task_output[TASK_OUTPUT_HEADER_SIZE..];
+
+            let task = if supported_cairo_verifier_program_hashes.contains(&program_hash) {
+                if size != COMPOSITE_TASK_OUTPUT_SIZE {
+                    return Err(BootloaderOutputError::InvalidCompositeTaskSize(
+                        task_index, size,
+                    ));
+                }
+                TaskOutput::Composite {
+                    cairo_verifier_program_hash: program_hash,
+                    simple_bootloader_program_hash: output[0],
+                    output_hash: output[1],
+                }
+            } else {
+                TaskOutput::Plain {
+                    program_hash,
+                    output: output.to_vec(),
+                }
+            };
+            tasks.push(task);
+
+            tasks_output = rest;
+            offset += size;
+        }
+
+        if !tasks_output.is_empty() {
+            return Err(BootloaderOutputError::TrailingOutput(tasks_output.len()));
+        }
+
+        Ok(Self { tasks })
+    }
+
+    /// Encodes the output back to the layout written by the bootloader.
+    pub fn encode(&self) -> Vec<Felt252> {
+        let mut encoded = vec![Felt252::from(self.tasks.len())];
+        for task in &self.tasks {
+            encoded.push(Felt252::from(task.size()));
+            encoded.push(*task.program_hash());
+            encoded.extend(task.output());
+        }
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn felts(values: &[u64]) -> Vec<Felt252> {
+        values.iter().copied().map(Felt252::from).collect()
+    }
+
+    #[test]
+    fn test_decode_plain_tasks() {
+        let output = felts(&[2, 4, 0x10, 7, 8, 2, 0x20]);
+
+        let bootloader_output = BootloaderOutput::decode(&output).unwrap();
+
+        assert_eq!(
+            bootloader_output.tasks,
+            vec![
+                TaskOutput::Plain {
+                    program_hash: Felt252::from(0x10),
+                    output: felts(&[7, 8]),
+                },
+                TaskOutput::Plain {
+                    program_hash: Felt252::from(0x20),
+                    output: vec![],
+                },
+            ]
+        );
+        assert_eq!(bootloader_output.encode(), output);
+    }
+
+    #[test]
+    fn test_decode_composite_task() {
+        let output = felts(&[2, 4, 0xaa, 0xbb, 0xcc, 3, 0x10, 7]);
+
+        let bootloader_output =
+            BootloaderOutput::decode_with_verifiers(&output, &[Felt252::from(0xaa)]).unwrap();
+
+        assert_eq!(
+            bootloader_output.tasks,
+            vec![
+                TaskOutput::Composite {
+                    cairo_verifier_program_hash: Felt252::from(0xaa),
+                    simple_bootloader_program_hash: Felt252::from(0xbb),
+                    output_hash: Felt252::from(0xcc),
+                },
+                TaskOutput::Plain {
+                    program_hash: Felt252::from(0x10),
+                    output: felts(&[7]),
+                },
+            ]
+        );
+        assert_eq!(bootloader_output.encode(), output);
+    }
+
+    #[test]
+    fn test_decode_no_tasks() {
+        let bootloader_output = BootloaderOutput::decode(&felts(&[0])).unwrap();
+        assert!(bootloader_output.tasks.is_empty());
+    }
+
+    #[rstest]
+    #[case::empty(vec![], BootloaderOutputError::EmptyOutput)]
+    #[case::task_out_of_bounds(
+        felts(&[1, 5, 0x10, 7]),
+        BootloaderOutputError::TaskOutputOutOfBounds(0, 6, 4)
+    )]
+    #[case::missing_task(
+        felts(&[2, 2, 0x10]),
+        BootloaderOutputError::TaskOutputOutOfBounds(1, 4, 3)
+    )]
+    #[case::trailing_output(felts(&[1, 3, 0x10, 7, 8]), BootloaderOutputError::TrailingOutput(1))]
+    #[case::invalid_size(
+        felts(&[1, 1, 0x10]),
+        BootloaderOutputError::InvalidTaskSize(0, Box::new(Felt252::ONE))
+    )]
+    #[case::invalid_composite_size(
+        felts(&[1, 3, 0xaa, 0xbb]),
+        BootloaderOutputError::InvalidCompositeTaskSize(0, 3)
+    )]
+    fn test_decode_invalid_output(
+        #[case] output: Vec<Felt252>,
+        #[case] expected_error: BootloaderOutputError,
+    ) {
+        let result = BootloaderOutput::decode_with_verifiers(&output, &[Felt252::from(0xaa)]);
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let bootloader_output = BootloaderOutput {
+            tasks: vec![TaskOutput::Plain {
+                program_hash: Felt252::from(0x10),
+                output: felts(&[7]),
+            }],
+        };
+
+        let json = serde_json::to_value(&bootloader_output).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "tasks": [{"type": "plain", "program_hash": "0x10", "output": ["0x7"]}]
+            })
+        );
+    }
+}
@@ -145,6 +145,13 @@ from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
 assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
     program=task.get_program(),
     use_poseidon=bool(ids.use_poseidon)), 'Computed hash does not match input.'";
+
+pub const EXECUTE_TASK_VALIDATE_HASH_BLAKE2S: &str = "# Validate hash.
+from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+
+assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
+    program=task.get_program(), use_blake2s=True), 'Computed hash does not match input.'";
+
 pub const EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS: &str = "# Sanity check.
 assert ids.program_address == program_address";
 
@@ -5,9 +5,8 @@ use cairo_vm::{
         builtin_hint_processor_definition::HintProcessorData,
         hint_utils::{get_address_from_var_name, get_integer_from_var_name},
     },
-    types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
+    types::relocatable::MaybeRelocatable,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
-    Felt252,
 };
 
 pub const PRINT_FELT_HEX: &str = "print(f\"{hex(ids.value)}\")";
@@ -17,50 +16,171 @@ pub const PRINT_UINT256: &str = "print(f\"{hex(ids.value.high * 2 ** 128 + ids.v
 pub const PRINT_UINT384: &str =
     "print(f\"{hex(ids.value.d3 * 2 ** 144 + ids.value.d2 * 2 ** 96 + ids.value.d1 * 2 ** 48 + ids.value.d0)}\")";
 
-pub fn print_felt_hex(
-    vm: &mut VirtualMachine,
-    _exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
+pub const INFO_FELT: &str = "print(f\"Info: {ids.value}\")";
+pub const INFO_FELT_HEX: &str = "print(f\"Info: {hex(ids.value)}\")";
+/// Same code as `INFO_FELT`, so `info_string` prints the felt in decimal, like the Python hint.
+/// This is also what `info_segment_hex` uses to print the index of each element.
+pub const INFO_STRING: &str = INFO_FELT;
+pub const INFO_UINT256: &str = "print(f\"Info: {hex(ids.value.high * 2**128 + ids.value.low)}\")";
+pub const INFO_UINT384: &str = "print(f\"Info: {hex(ids.value.d3 * 2 ** 144 + ids.value.d2 * 2 ** 96 + ids.value.d1 * 2 ** 48 + ids.value.d0)}\")";
+
+pub const DEBUG_FELT: &str = "print(f\"Debug: {ids.value}\")";
+pub const DEBUG_FELT_HEX: &str = "print(f\"Debug: {hex(ids.value)}\")";
+/// Same code as `DEBUG_FELT`, see `INFO_STRING`.
+pub const DEBUG_STRING: &str = DEBUG_FELT;
+pub const DEBUG_UINT256: &str = "print(f\"Debug: {hex(ids.value.high * 2**128 + ids.value.low)}\")";
+pub const DEBUG_UINT384: &str = "print(f\"Debug: {hex(ids.value.d3 * 2 ** 144 + ids.value.d2 * 2 ** 96 + ids.value.d1 * 2 ** 48 + ids.value.d0)}\")";
+
+/// Verbosity of the print hints of Cairo programs. `debug_*` hints only print at `Debug`, so
+/// they are no-ops at the default `Info` level. `Off` silences the `print_*` hints too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    #[default]
+    Info,
+    Debug,
+}
+
+/// A message printed by a Cairo program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugMessage {
+    /// Index of the task being run when the message was printed, `None` before the first task.
+    pub task_id: Option<usize>,
+    /// `Info` for the `info_*` and `print_*` hints, `Debug` for the `debug_*` hints.
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// Destination of the messages printed by Cairo programs.
+pub trait DebugSink {
+    fn log(&mut self, message: DebugMessage);
+
+    /// Returns the messages kept so far. Sinks that do not keep messages return none.
+    fn take_messages(&mut self) -> Vec<DebugMessage> {
+        Vec::new()
+    }
+}
+
+/// Keeps the messages, to be returned with the result of the run.
+#[derive(Debug, Default)]
+pub struct CollectSink {
+    messages: Vec<DebugMessage>,
+}
+
+impl DebugSink for CollectSink {
+    fn log(&mut self, message: DebugMessage) {
+        self.messages.push(message);
+    }
+
+    fn take_messages(&mut self) -> Vec<DebugMessage> {
+        std::mem::take(&mut self.messages)
+    }
+}
+
+/// Forwards the messages to `tracing`, as events of the `cairo` target.
+#[derive(Debug, Default)]
+pub struct TracingSink;
+
+impl DebugSink for TracingSink {
+    fn log(&mut self, message: DebugMessage) {
+        match message.level {
+            LogLevel::Off => {}
+            LogLevel::Info => {
+                tracing::info!(target: "cairo", task_id = message.task_id, "{}", message.message)
+            }
+            LogLevel::Debug => {
+                tracing::debug!(target: "cairo", task_id = message.task_id, "{}", message.message)
+            }
+        }
+    }
+}
+
+/// Drops the messages.
+#[derive(Debug, Default)]
+pub struct NullSink;
+
+impl DebugSink for NullSink {
+    fn log(&mut self, _message: DebugMessage) {}
+}
+
+/// Formats the message of a print hint.
+pub type DebugHintImpl = fn(&VirtualMachine, &HintProcessorData) -> Result<String, HintError>;
+
+/// A print hint, which only reads the memory when the log level is at least `level`.
+#[derive(Clone, Copy)]
+pub struct DebugHint {
+    pub level: LogLevel,
+    pub format: DebugHintImpl,
+}
+
+impl DebugHint {
+    const fn new(level: LogLevel, format: DebugHintImpl) -> Self {
+        Self { level, format }
+    }
+}
+
+pub fn get_debug_hints() -> HashMap<String, DebugHint> {
+    let mut hints = HashMap::new();
+    hints.insert(
+        PRINT_FELT_HEX.into(),
+        DebugHint::new(LogLevel::Info, felt_hex),
+    );
+    hints.insert(PRINT_FELT.into(), DebugHint::new(LogLevel::Info, felt));
+    hints.insert(PRINT_STRING.into(), DebugHint::new(LogLevel::Info, string));
+    hints.insert(
+        PRINT_UINT256.into(),
+        DebugHint::new(LogLevel::Info, uint256),
+    );
+    hints.insert(
+        PRINT_UINT384.into(),
+        DebugHint::new(LogLevel::Info, uint384),
+    );
+
+    hints.insert(INFO_FELT.into(), DebugHint::new(LogLevel::Info, felt));
+    hints.insert(
+        INFO_FELT_HEX.into(),
+        DebugHint::new(LogLevel::Info, felt_hex),
+    );
+    hints.insert(INFO_UINT256.into(), DebugHint::new(LogLevel::Info, uint256));
+    hints.insert(INFO_UINT384.into(), DebugHint::new(LogLevel::Info, uint384));
+
+    hints.insert(DEBUG_FELT.into(), DebugHint::new(LogLevel::Debug, felt));
+    hints.insert(
+        DEBUG_FELT_HEX.into(),
+        DebugHint::new(LogLevel::Debug, felt_hex),
+    );
+    hints.insert(
+        DEBUG_UINT256.into(),
+        DebugHint::new(LogLevel::Debug, uint256),
+    );
+    hints.insert(
+        DEBUG_UINT384.into(),
+        DebugHint::new(LogLevel::Debug, uint384),
+    );
+    hints
+}
+
+pub fn felt(vm: &VirtualMachine, hint_data: &HintProcessorData) -> Result<String, HintError> {
     let value =
         get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-    println!("Value: {}", value.to_hex_string());
-    Ok(())
+    Ok(value.to_string())
 }
 
-pub fn print_felt(
-    vm: &mut VirtualMachine,
-    _exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
+pub fn felt_hex(vm: &VirtualMachine, hint_data: &HintProcessorData) -> Result<String, HintError> {
     let value =
         get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-    println!("Value: {value}");
-    Ok(())
+    Ok(value.to_hex_string())
 }
 
-pub fn print_string(
-    vm: &mut VirtualMachine,
-    _exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
+pub fn string(vm: &VirtualMachine, hint_data: &HintProcessorData) -> Result<String, HintError> {
     let value =
         get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
     let bytes = value.to_bytes_be();
-    let ascii = String::from_utf8_lossy(&bytes);
-    println!("String: {ascii}");
-    Ok(())
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    Ok(String::from_utf8_lossy(&bytes[start..]).into_owned())
 }
 
-pub fn print_uint256(
-    vm: &mut VirtualMachine,
-    _exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
+pub fn uint256(vm: &VirtualMachine, hint_data: &HintProcessorData) -> Result<String, HintError> {
     let ptr: MaybeRelocatable =
         get_address_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
     if let MaybeRelocatable::RelocatableValue(ptr) = ptr {
@@ -76,20 +196,14 @@ pub fn print_uint256(
         let mut bytes = Vec::new();
         bytes.extend_from_slice(high_128);
         bytes.extend_from_slice(low_128);
-        println!("Value: 0x{}", hex::encode(bytes));
-        return Ok(());
+        return Ok(format!("0x{}", hex::encode(bytes)));
     }
     Err(HintError::UnknownHint(
         hint_data.code.to_string().into_boxed_str(),
     ))
 }
 
-pub fn print_uint384(
-    vm: &mut VirtualMachine,
-    _exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
+pub fn uint384(vm: &VirtualMachine, hint_data: &HintProcessorData) -> Result<String, HintError> {
     let ptr: MaybeRelocatable =
         get_address_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
     if let MaybeRelocatable::RelocatableValue(ptr) = ptr {
@@ -102,231 +216,70 @@ pub fn print_uint384(
         bytes.extend_from_slice(&d2.to_bytes_be());
         bytes.extend_from_slice(&d1.to_bytes_be());
         bytes.extend_from_slice(&d0.to_bytes_be());
-        println!("Value: 0x{}", hex::encode(bytes));
+        return Ok(format!("0x{}", hex::encode(bytes)));
     }
-    Ok(())
-}
-
-pub const INFO_FELT: &str = "print(f\"Info: {ids.value}\")";
-pub const INFO_FELT_HEX: &str = "print(f\"Info: {hex(ids.value)}\")";
-pub const INFO_STRING: &str = "print(f\"Info: {ids.value}\")";
-pub const INFO_UINT256: &str = "print(f\"Info: {hex(ids.value.high * 2**128 + ids.value.low)}\")";
-pub const INFO_UINT384: &str = "print(f\"Info: {hex(ids.value.d3 * 2 ** 144 + ids.value.d2 * 2 ** 96 + ids.value.d1 * 2 ** 48 + ids.value.d0)}\")";
-
-pub const DEBUG_FELT: &str = "print(f\"Debug: {ids.value}\")";
-pub const DEBUG_FELT_HEX: &str = "print(f\"Debug: {hex(ids.value)}\")";
-pub const DEBUG_STRING: &str = "print(f\"Debug: {ids.value}\")";
-pub const DEBUG_UINT256: &str = "print(f\"Debug: {hex(ids.value.high * 2**128 + ids.value.low)}\")";
-pub const DEBUG_UINT384: &str = "print(f\"Debug: {hex(ids.value.d3 * 2 ** 144 + ids.value.d2 * 2 ** 96 + ids.value.d1 * 2 ** 48 + ids.value.d0)}\")";
-
-pub fn info_felt(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "info" || log_level == "debug" {
-        let value =
-            get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        println!("Info: {value}");
-    }
-    Ok(())
+    Err(HintError::UnknownHint(
+        hint_data.code.to_string().into_boxed_str(),
+    ))
 }
 
-pub fn info_felt_hex(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "info" || log_level == "debug" {
-        let value =
-            get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        println!("Info: {}", value.to_hex_string());
-    }
-    Ok(())
-}
+#[cfg(test)]
+mod tests {
+    use cairo_vm::Felt252;
+    use rstest::rstest;
 
-pub fn info_string(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "info" || log_level == "debug" {
-        let value =
-            get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        let bytes = value.to_bytes_be();
-        let ascii = String::from_utf8_lossy(&bytes);
-        println!("Info: {ascii}");
-    }
-    Ok(())
-}
+    use super::*;
+    use crate::{add_segments, ids_data, vm};
 
-pub fn info_uint256(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "info" || log_level == "debug" {
-        let ptr: MaybeRelocatable =
-            get_address_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        if let MaybeRelocatable::RelocatableValue(ptr) = ptr {
-            let low = vm.get_integer((ptr + 0)?)?;
-            let high = vm.get_integer((ptr + 1)?)?;
-
-            let low_bytes = low.to_bytes_be();
-            let high_bytes = high.to_bytes_be();
-
-            let low_128 = &low_bytes[low_bytes.len().saturating_sub(16)..];
-            let high_128 = &high_bytes[high_bytes.len().saturating_sub(16)..];
-
-            let mut bytes = Vec::new();
-            bytes.extend_from_slice(high_128);
-            bytes.extend_from_slice(low_128);
-            println!("Info: 0x{}", hex::encode(bytes));
-            return Ok(());
-        }
-        return Err(HintError::UnknownHint(
-            hint_data.code.to_string().into_boxed_str(),
-        ));
-    }
-    Ok(())
-}
+    /// The messages of `info_segment_hex` on `[0xa, 0xb]`, which calls `info_string` on the
+    /// index and `info_felt_hex` on the element.
+    #[rstest]
+    fn test_segment_hex() {
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        let ids_data = ids_data!["value"];
+        let debug_hints = get_debug_hints();
 
-pub fn info_uint384(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "info" || log_level == "debug" {
-        let ptr: MaybeRelocatable =
-            get_address_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        if let MaybeRelocatable::RelocatableValue(ptr) = ptr {
-            let d0 = vm.get_integer((ptr + 0)?)?;
-            let d1 = vm.get_integer((ptr + 1)?)?;
-            let d2 = vm.get_integer((ptr + 2)?)?;
-            let d3 = vm.get_integer((ptr + 3)?)?;
-            let mut bytes = Vec::new();
-            bytes.extend_from_slice(&d3.to_bytes_be());
-            bytes.extend_from_slice(&d2.to_bytes_be());
-            bytes.extend_from_slice(&d1.to_bytes_be());
-            bytes.extend_from_slice(&d0.to_bytes_be());
-            println!("Info: 0x{}", hex::encode(bytes));
+        let calls = [
+            (INFO_STRING, 0),
+            (INFO_FELT_HEX, 0xa),
+            (INFO_STRING, 1),
+            (INFO_FELT_HEX, 0xb),
+        ];
+        let mut messages = vec![];
+        for (fp, (code, value)) in calls.into_iter().enumerate() {
+            vm.insert_value((1, fp).into(), Felt252::from(value))
+                .expect("Could not insert the value");
+            vm.set_fp(fp + 1);
+            let hint_data = HintProcessorData::new_default(code.to_string(), ids_data.clone());
+            let debug_hint = debug_hints[code];
+            assert_eq!(debug_hint.level, LogLevel::Info);
+            messages.push((debug_hint.format)(&vm, &hint_data).expect("Hint failed"));
         }
-    }
-    Ok(())
-}
 
-pub fn debug_felt(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "debug" {
-        let value =
-            get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        println!("Debug: {value}");
+        assert_eq!(messages, ["0", "0xa", "1", "0xb"]);
     }
-    Ok(())
-}
 
-pub fn debug_felt_hex(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "debug" {
-        let value =
-            get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        println!("Debug: {}", value.to_hex_string());
+    #[rstest]
+    #[case::info(INFO_FELT, LogLevel::Info)]
+    #[case::debug(DEBUG_FELT_HEX, LogLevel::Debug)]
+    #[case::print(PRINT_STRING, LogLevel::Info)]
+    fn test_debug_hint_level(#[case] code: &str, #[case] level: LogLevel) {
+        assert_eq!(get_debug_hints()[code].level, level);
     }
-    Ok(())
-}
 
-pub fn debug_string(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "debug" {
-        let value =
-            get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        let bytes = value.to_bytes_be();
-        let ascii = String::from_utf8_lossy(&bytes);
-        println!("Debug: {ascii}");
-    }
-    Ok(())
-}
+    #[rstest]
+    fn test_collect_sink() {
+        let mut sink = CollectSink::default();
+        let message = DebugMessage {
+            task_id: Some(1),
+            level: LogLevel::Debug,
+            message: "0x1".to_string(),
+        };
+        sink.log(message.clone());
 
-pub fn debug_uint256(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "debug" {
-        let ptr: MaybeRelocatable =
-            get_address_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        if let MaybeRelocatable::RelocatableValue(ptr) = ptr {
-            let low = vm.get_integer((ptr + 0)?)?;
-            let high = vm.get_integer((ptr + 1)?)?;
-
-            let low_bytes = low.to_bytes_be();
-            let high_bytes = high.to_bytes_be();
-
-            let low_128 = &low_bytes[low_bytes.len().saturating_sub(16)..];
-            let high_128 = &high_bytes[high_bytes.len().saturating_sub(16)..];
-
-            let mut bytes = Vec::new();
-            bytes.extend_from_slice(high_128);
-            bytes.extend_from_slice(low_128);
-            println!("Debug: 0x{}", hex::encode(bytes));
-            return Ok(());
-        }
-        return Err(HintError::UnknownHint(
-            hint_data.code.to_string().into_boxed_str(),
-        ));
+        assert_eq!(sink.take_messages(), vec![message]);
+        assert!(sink.take_messages().is_empty());
+        assert!(NullSink.take_messages().is_empty());
     }
-    Ok(())
 }
-
-pub fn debug_uint384(
-    vm: &mut VirtualMachine,
-    exec_scopes: &mut ExecutionScopes,
-    hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
-) -> Result<(), HintError> {
-    let log_level = exec_scopes.get::<&str>("LOG_LEVEL_CAIRO").unwrap_or("info");
-    if log_level == "debug" {
-        let ptr: MaybeRelocatable =
-            get_address_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-        if let MaybeRelocatable::RelocatableValue(ptr) = ptr {
-            let d0 = vm.get_integer((ptr + 0)?)?;
-            let d1 = vm.get_integer((ptr + 1)?)?;
-            let d2 = vm.get_integer((ptr + 2)?)?;
-            let d3 = vm.get_integer((ptr + 3)?)?;
-            let mut bytes = Vec::new();
-            bytes.extend_from_slice(&d3.to_bytes_be());
-            bytes.extend_from_slice(&d2.to_bytes_be());
-            bytes.extend_from_slice(&d1.to_bytes_be());
-            bytes.extend_from_slice(&d0.to_bytes_be());
-            println!("Debug: 0x{}", hex::encode(bytes));
-        }
-    }
-    Ok(())
-}
\ No newline at end of file
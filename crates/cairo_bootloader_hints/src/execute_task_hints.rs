@@ -4,16 +4,18 @@ use std::collections::HashMap;
 use cairo_lang_casm::hints::Hint;
 use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData;
 use cairo_vm::hint_processor::builtin_hint_processor::hint_utils::{
-    get_ptr_from_var_name, get_relocatable_from_var_name, insert_value_from_var_name,
+    get_integer_from_var_name, get_ptr_from_var_name, get_relocatable_from_var_name,
+    insert_value_from_var_name,
 };
 use cairo_vm::hint_processor::hint_processor_definition::{HintExtension, HintProcessor};
-use cairo_vm::serde::deserialize_program::{HintParams, Identifier};
+use cairo_vm::serde::deserialize_program::HintParams;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::types::exec_scope::ExecutionScopes;
 use cairo_vm::types::program::Program;
 use cairo_vm::types::relocatable::Relocatable;
 use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::vm::errors::memory_errors::MemoryError;
+use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
 use cairo_vm::vm::runners::builtin_runner::{OutputBuiltinRunner, OutputBuiltinState};
 use cairo_vm::vm::runners::cairo_pie::{CairoPie, StrippedProgram};
 use cairo_vm::vm::vm_core::VirtualMachine;
@@ -22,7 +24,7 @@ use starknet_crypto::FieldElement;
 
 use crate::fact_topologies::{get_task_fact_topology, FactTopology};
 use crate::load_cairo_pie::load_cairo_pie;
-use crate::program_hash::compute_program_hash_chain;
+use crate::program_hash::{compute_program_hash_chain, ProgramHashFunction};
 use crate::program_loader::ProgramLoader;
 use crate::types::TaskSpec;
 use crate::types::{BootloaderVersion, ProgramIdentifiers, Task};
@@ -30,6 +32,40 @@ use crate::vars;
 
 use super::types::{CairoPieTask, RunProgramTask};
 
+/// Version of the bootloader, written in the header of every loaded program and included in
+/// its hash. Must match `BOOTLOADER_VERSION` in execute_task.cairo.
+pub const BOOTLOADER_VERSION: BootloaderVersion = 0;
+
+#[derive(thiserror_no_std::Error, Debug)]
+pub enum ExecuteTaskError {
+    #[error("The current task is not set")]
+    NoTask,
+
+    #[error("Task {task_id}: the hint ranges of the program refer to hint {hint_index}, but the program has {n_hints} hints")]
+    HintOutOfBounds {
+        task_id: usize,
+        hint_index: usize,
+        n_hints: usize,
+    },
+
+    #[error("Task {task_id}: failed to compile the hint at pc {pc}: {error}\nHint code: {code}")]
+    HintCompilation {
+        task_id: usize,
+        pc: Relocatable,
+        code: String,
+        error: Box<VirtualMachineError>,
+    },
+
+    #[error("The bootloader program has no `{0}` label, Cairo PIE tasks cannot return to it")]
+    MissingBootloaderLabel(String),
+}
+
+impl From<ExecuteTaskError> for HintError {
+    fn from(value: ExecuteTaskError) -> Self {
+        HintError::CustomHint(value.to_string().into_boxed_str())
+    }
+}
+
 fn get_stripped_program_from_task(task: &Box<dyn Task>) -> Result<StrippedProgram, HintError> {
     task.get_program()
         .map_err(|e| HintError::CustomHint(e.to_string().into_boxed_str()))
@@ -48,9 +84,8 @@ fn get_task_from_exec_scopes(exec_scopes: &ExecutionScopes) -> Result<Box<dyn Ta
     let local_variables: &HashMap<String, Box<dyn Any>> = exec_scopes.get_local_variables()?;
     let task_spec: &TaskSpec = local_variables
         .get(vars::TASK)
-        .unwrap()
-        .downcast_ref::<TaskSpec>()
-        .unwrap();
+        .and_then(|task| task.downcast_ref::<TaskSpec>())
+        .ok_or(ExecuteTaskError::NoTask)?;
     let task = task_spec
         .load_task()
         .map_err(|e| HintError::CustomHint(e.to_string().into_boxed_str()))?;
@@ -79,7 +114,7 @@ pub fn allocate_program_data_segment(
     Ok(())
 }
 
-fn field_element_to_felt(field_element: FieldElement) -> Felt252 {
+pub(crate) fn field_element_to_felt(field_element: FieldElement) -> Felt252 {
     let bytes = field_element.to_bytes_be();
     Felt252::from_bytes_be(&bytes)
 }
@@ -99,10 +134,8 @@ pub fn load_program_hint(
     hint_data: &HintProcessorData,
     _constants: &HashMap<String, Felt252>,
 ) -> Result<(), HintError> {
-    println!("Loading program hint fn");
     let program_data_base: Relocatable = exec_scopes.get(vars::PROGRAM_DATA_BASE)?;
     let task = get_task_from_exec_scopes(exec_scopes)?;
-    println!("Task obtained");
     let program = get_stripped_program_from_task(&task)?;
 
     let program_header_ptr = get_ptr_from_var_name(
@@ -115,10 +148,8 @@ pub fn load_program_hint(
     // Offset of the builtin_list field in `ProgramHeader`, cf. execute_task.cairo
     let builtins_offset = 4;
     let mut program_loader = ProgramLoader::new(vm, builtins_offset);
-    println!("Program loader created");
-    let bootloader_version: BootloaderVersion = 0;
     let loaded_program = program_loader
-        .load_program(program_header_ptr, &program, Some(bootloader_version))
+        .load_program(program_header_ptr, &program, Some(BOOTLOADER_VERSION))
         .map_err(Into::<HintError>::into)?;
 
     vm.segments.finalize(
@@ -127,6 +158,11 @@ pub fn load_program_hint(
         None,
     );
 
+    tracing::debug!(
+        program_address = %loaded_program.code_address,
+        size = loaded_program.size,
+        "Loaded the task program"
+    );
     exec_scopes.insert_value(vars::PROGRAM_ADDRESS, loaded_program.code_address);
 
     Ok(())
@@ -182,18 +218,13 @@ pub fn append_fact_topologies(
     Ok(())
 }
 
-/// Implements
-/// # Validate hash.
-/// from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
-///
-/// assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
-///     program=task.get_program(),
-///     use_poseidon=bool(ids.use_poseidon)), 'Computed hash does not match input.'
-pub fn validate_hash(
+/// Checks that the program hash written to the output matches the hash of the task program,
+/// computed with `hash_function`.
+fn validate_program_hash(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     hint_data: &HintProcessorData,
-    _constants: &HashMap<String, Felt252>,
+    hash_function: ProgramHashFunction,
 ) -> Result<(), HintError> {
     let task = get_task_from_exec_scopes(exec_scopes)?;
     let program = get_stripped_program_from_task(&task)?;
@@ -209,9 +240,10 @@ pub fn validate_hash(
     let program_hash = vm.get_integer(program_hash_ptr)?.into_owned();
 
     // Compute the hash of the program
-    let computed_program_hash = compute_program_hash_chain(&program, 0).map_err(|e| {
-        HintError::CustomHint(format!("Could not compute program hash: {e}").into_boxed_str())
-    })?;
+    let computed_program_hash =
+        compute_program_hash_chain(&program, BOOTLOADER_VERSION, hash_function).map_err(|e| {
+            HintError::CustomHint(format!("Could not compute program hash: {e}").into_boxed_str())
+        })?;
     let computed_program_hash = field_element_to_felt(computed_program_hash);
 
     if program_hash != computed_program_hash {
@@ -225,6 +257,60 @@ pub fn validate_hash(
     Ok(())
 }
 
+/// Implements
+/// # Validate hash.
+/// from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+///
+/// assert memory[ids.output_ptr + 1] == compute_program_hash_chain(task.get_program()), \
+///   'Computed hash does not match input.'
+pub fn validate_hash_v0_13_0(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    hint_data: &HintProcessorData,
+    _constants: &HashMap<String, Felt252>,
+) -> Result<(), HintError> {
+    validate_program_hash(vm, exec_scopes, hint_data, ProgramHashFunction::Pedersen)
+}
+
+/// Implements
+/// # Validate hash.
+/// from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+///
+/// assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
+///     program=task.get_program(),
+///     use_poseidon=bool(ids.use_poseidon)), 'Computed hash does not match input.'
+pub fn validate_hash(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    hint_data: &HintProcessorData,
+    _constants: &HashMap<String, Felt252>,
+) -> Result<(), HintError> {
+    let use_poseidon = get_integer_from_var_name(
+        "use_poseidon",
+        vm,
+        &hint_data.ids_data,
+        &hint_data.ap_tracking,
+    )?;
+    let hash_function = ProgramHashFunction::from_use_poseidon(use_poseidon != Felt252::ZERO);
+
+    validate_program_hash(vm, exec_scopes, hint_data, hash_function)
+}
+
+/// Implements
+/// # Validate hash.
+/// from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+///
+/// assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
+///     program=task.get_program(), use_blake2s=True), 'Computed hash does not match input.'
+pub fn validate_hash_blake2s(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    hint_data: &HintProcessorData,
+    _constants: &HashMap<String, Felt252>,
+) -> Result<(), HintError> {
+    validate_program_hash(vm, exec_scopes, hint_data, ProgramHashFunction::Blake2s)
+}
+
 /// List of all builtins in the order used by the bootloader.
 pub const ALL_BUILTINS: [BuiltinName; 11] = [
     BuiltinName::output,
@@ -253,7 +339,16 @@ fn check_cairo_pie_builtin_usage(
         vm.get_relocatable((pre_execution_builtins_addr + builtin_index)?)?;
     let expected_builtin_size = (return_builtin_value - pre_execution_builtin_value)?;
 
-    let builtin_size = cairo_pie.metadata.builtin_segments[builtin_name].size;
+    let builtin_size = cairo_pie
+        .metadata
+        .builtin_segments
+        .get(builtin_name)
+        .ok_or_else(|| {
+            HintError::AssertionFailed(
+                format!("The CairoPie has no {builtin_name} builtin segment.").into_boxed_str(),
+            )
+        })?
+        .size;
 
     if builtin_size != expected_builtin_size {
         return Err(HintError::AssertionFailed(
@@ -397,19 +492,15 @@ fn get_bootloader_identifiers(
     ))
 }
 
-fn get_identifier(
-    identifiers: &HashMap<String, Identifier>,
-    name: &str,
-) -> Result<usize, HintError> {
-    if let Some(identifier) = identifiers.get(name) {
-        if let Some(pc) = identifier.pc {
-            return Ok(pc);
-        }
-    }
+/// Full names of the labels around the `call_task` hint in `execute_task`.
+pub const CALL_TASK_LABEL: &str = "bootloader.execute_task.execute_task.call_task";
+pub const RET_PC_LABEL: &str = "bootloader.execute_task.execute_task.ret_pc_label";
 
-    Err(HintError::VariableNotInScopeError(
-        name.to_string().into_boxed_str(),
-    ))
+fn get_identifier(identifiers: &ProgramIdentifiers, name: &str) -> Result<usize, ExecuteTaskError> {
+    identifiers
+        .get(name)
+        .and_then(|identifier| identifier.pc)
+        .ok_or_else(|| ExecuteTaskError::MissingBootloaderLabel(name.to_string()))
 }
 
 /*
@@ -451,7 +542,6 @@ Implements hint:
 */
 pub fn call_task(
     hint_processor: &mut dyn HintProcessor,
-    hint_map: &HashMap<String, Hint>,
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     hint_data: &HintProcessorData,
@@ -469,28 +559,40 @@ pub fn call_task(
         let program_input = run_program_task.program_input.clone();
         // new_task_locals['program_input'] = task.program_input
         new_task_locals.insert("program_input".to_string(), any_box![program_input]);
-        println!("Program input inserted");
+        // Not in the Python hint: the run params of Cairo 1 tasks are task-scoped so that tasks
+        // run in the same bootloader each read their own.
+        let user_args = run_program_task.user_args.clone();
+        new_task_locals.insert(vars::USER_ARGS.to_string(), any_box![user_args]);
+        let task_id: usize = exec_scopes.get(vars::TASK_ID)?;
+        new_task_locals.insert(vars::TASK_ID.to_string(), any_box![task_id]);
+        // Not in the Python hint either: the panic traceback of a Cairo 1 task is relocated
+        // against the address of its program.
+        let program_address: Relocatable = exec_scopes.get(vars::PROGRAM_ADDRESS)?;
+        new_task_locals.insert(vars::PROGRAM_ADDRESS.to_string(), any_box![program_address]);
         // new_task_locals['WITH_BOOTLOADER'] = True
         new_task_locals.insert("WITH_BOOTLOADER".to_string(), any_box![true]);
-        println!("WITH_BOOTLOADER inserted");
         // TODO: the content of this function is mostly useless for the Rust VM.
         //       check with SW if there is nothing of interest here.
         // vm_load_program(task.program, program_address)
-        let task_hint_extension =
-            vm_load_program(hint_processor, hint_map, vm, exec_scopes, hint_data, _constants)?;
-        println!("Task hint extension received");
+        // The hints of the task are resolved against its own hint table, tasks compiled from
+        // different programs may use the same hint string for different hints.
+        let task_hint_extension = vm_load_program(
+            hint_processor,
+            &run_program_task.hints,
+            vm,
+            exec_scopes,
+            hint_data,
+            _constants,
+        )?;
         hint_extension.extend(task_hint_extension);
     } else if let Some(cairo_pie_task) = task.as_any().downcast_ref::<CairoPieTask>() {
         let program_address: Relocatable = exec_scopes.get("program_address")?;
 
         // ret_pc = ids.ret_pc_label.instruction_offset_ - ids.call_task.instruction_offset_ + pc
-        // TODO: replace with proper way of getting `ret_pc_label` and `call_task` labels from `cairo-vm`
-        // Temporary solution:
-        //   `starkware.cairo.bootloaders.simple_bootloader.execute_task.execute_task.ret_pc_label` is a label at pc=279 and
-        //   `starkware.cairo.bootloaders.simple_bootloader.execute_task.execute_task.call_task` is a label at pc=278
-        //   And since this hint is called at pc=278, `ret_pc` can be calculated as:
-        //     ret_pc = 279 - 278 + pc = 1 + pc
-        let ret_pc = (vm.get_pc() + 1)?;
+        let bootloader_identifiers = get_bootloader_identifiers(exec_scopes)?;
+        let ret_pc_label = get_identifier(bootloader_identifiers, RET_PC_LABEL)?;
+        let call_task_label = get_identifier(bootloader_identifiers, CALL_TASK_LABEL)?;
+        let ret_pc = ((vm.get_pc() + ret_pc_label)? - call_task_label)?;
 
         // load_cairo_pie(
         //     task=task.cairo_pie, memory=memory, segments=segments,
@@ -530,24 +632,29 @@ pub fn call_task(
     exec_scopes.insert_value(vars::OUTPUT_RUNNER_DATA, output_runner_data);
 
     exec_scopes.enter_scope(new_task_locals);
-    println!("Task locals inserted");
+    tracing::debug!(
+        n_builtins,
+        n_hint_pcs = hint_extension.len(),
+        "Calling the task"
+    );
 
     Ok(hint_extension)
 }
 
+/// Compiles the hints of the task program, relocated to `program_address`. Hints found in
+/// `task_hints` are Cairo 1 hints and run as is, the others are compiled as Cairo 0 hints.
 fn vm_load_program(
-    _hint_processor: &mut dyn HintProcessor,
-    hint_map: &HashMap<String, Hint>,
+    hint_processor: &mut dyn HintProcessor,
+    task_hints: &HashMap<String, Hint>,
     _vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     _hint_data: &HintProcessorData,
     _constants: &HashMap<String, Felt252>,
 ) -> Result<HintExtension, HintError> {
-    println!("vm_load_program");
-    let task_program_address: Relocatable = exec_scopes.get(vars::PROGRAM_ADDRESS).unwrap();
+    let task_program_address: Relocatable = exec_scopes.get(vars::PROGRAM_ADDRESS)?;
+    let task_id: usize = exec_scopes.get(vars::TASK_ID)?;
     let task = get_task_from_exec_scopes(exec_scopes)?;
     let task_program = get_program_from_task(&task)?;
-    println!("Task program obtained");
 
     let mut task_program_compiled_hints: HintExtension = HashMap::new();
     let task_program_hints = task_program
@@ -559,20 +666,44 @@ fn vm_load_program(
         .shared_program_data
         .hints_collection
         .hints_ranges;
+    let task_program_references = &task_program.shared_program_data.reference_manager;
 
     for (hint_pc, (s, l)) in task_program_hint_ranges.iter() {
         for idx in *s..(*s + l.get()) {
-            let hint_param: &HintParams = task_program_hints.get(idx).unwrap();
-            let hint = hint_map.get(&hint_param.code).unwrap();
+            let hint_param: &HintParams =
+                task_program_hints
+                    .get(idx)
+                    .ok_or(ExecuteTaskError::HintOutOfBounds {
+                        task_id,
+                        hint_index: idx,
+                        n_hints: task_program_hints.len(),
+                    })?;
 
             let new_hint_pc_segment = hint_pc.segment_index + task_program_address.segment_index;
             let new_hint_pc_offset = hint_pc.offset + task_program_address.offset;
             let new_hint_pc = Relocatable::from((new_hint_pc_segment, new_hint_pc_offset));
 
+            let compiled_hint: Box<dyn Any> = match task_hints.get(&hint_param.code) {
+                Some(hint) => Box::new(hint.clone()),
+                None => hint_processor
+                    .compile_hint(
+                        &hint_param.code,
+                        &hint_param.flow_tracking_data.ap_tracking,
+                        &hint_param.flow_tracking_data.reference_ids,
+                        task_program_references,
+                    )
+                    .map_err(|error| ExecuteTaskError::HintCompilation {
+                        task_id,
+                        pc: *hint_pc,
+                        code: hint_param.code.clone(),
+                        error: Box::new(error),
+                    })?,
+            };
+
             task_program_compiled_hints
                 .entry(new_hint_pc)
                 .or_insert_with(Vec::new)
-                .push(Box::new(hint.clone()));
+                .push(compiled_hint);
         }
     }
 
@@ -676,7 +807,12 @@ mod tests {
 
     use rstest::{fixture, rstest};
 
-    use crate::codes::EXECUTE_TASK_CALL_TASK;
+    use cairo_lang_casm::hints::{CoreHint, CoreHintBase};
+    use cairo_lang_casm::operand::{CellRef, Register};
+    use cairo_vm::serde::deserialize_program::{ApTracking, FlowTrackingData, Identifier};
+
+    use crate::codes::{EXECUTE_TASK_CALL_TASK, EXECUTE_TASK_VALIDATE_HASH_BLAKE2S};
+    use crate::types::UserArg;
 
     use crate::{
         add_segments, define_segments, ids_data, non_continuous_ids_data, run_hint, vm,
@@ -826,8 +962,13 @@ mod tests {
         // Cairo program if needed.
         let program_address = Relocatable::from((2, 0));
         exec_scopes.insert_value(vars::PROGRAM_ADDRESS, program_address);
+        exec_scopes.insert_value(vars::TASK_ID, 0_usize);
 
-        let task = TaskSpec::RunProgram(RunProgramTask::new(fibonacci, HashMap::new(), false));
+        let args = vec![UserArg::Value(Felt252::from(10))];
+        let user_args = vec![vec![UserArg::Array(args)]];
+        let task = TaskSpec::RunProgram(
+            RunProgramTask::new(fibonacci, HashMap::new(), false).with_user_args(user_args.clone()),
+        );
         exec_scopes.insert_box(vars::TASK, Box::new(task));
 
         assert_matches!(
@@ -839,6 +980,13 @@ mod tests {
             ),
             Ok(map) if map.is_empty()
         );
+
+        // The run params of the task are exposed in the task scope
+        let task_user_args: &Vec<Vec<UserArg>> = exec_scopes.get_ref(vars::USER_ARGS).unwrap();
+        assert_eq!(task_user_args, &user_args);
+        // So is the address of its program
+        let task_program_address: Relocatable = exec_scopes.get(vars::PROGRAM_ADDRESS).unwrap();
+        assert_eq!(task_program_address, program_address);
     }
 
     #[rstest]
@@ -864,6 +1012,7 @@ mod tests {
         // Cairo program if needed.
         let program_address = Relocatable::from((2, 0));
         exec_scopes.insert_value(vars::PROGRAM_ADDRESS, program_address);
+        exec_scopes.insert_value(vars::TASK_ID, 0_usize);
 
         let task = TaskSpec::RunProgram(RunProgramTask::new(
             fibonacci_with_hint.clone(),
@@ -900,15 +1049,107 @@ mod tests {
         .unwrap();
 
         let actual_hint_map_value = actual_hint_map.get(&expected_hint_map_key).unwrap();
-        let actual_hint = actual_hint_map_value.hints[0]
+        let actual_hint = actual_hint_map_value[0]
             .downcast_ref::<HintProcessorData>()
             .unwrap();
-        assert_eq!(actual_hint_map_value.hints.len(), 1);
+        assert_eq!(actual_hint_map_value.len(), 1);
         assert_eq!(actual_hint.code, compiled_hint.code);
         assert_eq!(actual_hint.ap_tracking, compiled_hint.ap_tracking);
         assert_eq!(actual_hint.ids_data, compiled_hint.ids_data);
     }
 
+    /// Builds a program with a single hint at pc 0.
+    fn program_with_hint(hint_code: &str, reference_ids: HashMap<String, usize>) -> Program {
+        let hint_params = HintParams {
+            code: hint_code.to_string(),
+            accessible_scopes: vec![],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids,
+            },
+        };
+        let ret = Felt252::from(0x208b7fff7fff7ffe_u64);
+        Program::new(
+            vec![],
+            vec![MaybeRelocatable::from(ret)],
+            Some(0),
+            HashMap::from([(0, vec![hint_params])]),
+            Default::default(),
+            Default::default(),
+            vec![],
+            None,
+        )
+        .unwrap()
+    }
+
+    /// Builds a Cairo 1 task with a single hint at pc 0, keyed by `hint_code`.
+    fn cairo1_task(hint_code: &str, hint: Hint) -> TaskSpec {
+        let program = program_with_hint(hint_code, HashMap::new());
+        TaskSpec::RunProgram(
+            RunProgramTask::new(program, HashMap::new(), false)
+                .with_hints(HashMap::from([(hint_code.to_string(), hint)])),
+        )
+    }
+
+    /// Runs `call_task` for `task`, loaded at (2, 0), as task `task_id`.
+    fn run_call_task(task: TaskSpec, task_id: usize) -> Result<HintExtension, HintError> {
+        let mut vm = vm!();
+        define_segments!(vm, 2, [((1, 0), (2, 0))]);
+        vm.set_fp(8);
+        add_segments!(vm, 1);
+        let ids_data = non_continuous_ids_data![(vars::PRE_EXECUTION_BUILTIN_PTRS, -8)];
+
+        let mut output_builtin = OutputBuiltinRunner::new(true);
+        output_builtin.initialize_segments(&mut vm.segments);
+        vm.builtin_runners
+            .push(BuiltinRunner::Output(output_builtin));
+
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(vars::PROGRAM_ADDRESS, Relocatable::from((2, 0)));
+        exec_scopes.insert_value(vars::TASK_ID, task_id);
+        exec_scopes.insert_box(vars::TASK, Box::new(task));
+
+        run_hint!(vm, ids_data, EXECUTE_TASK_CALL_TASK, &mut exec_scopes)
+    }
+
+    #[test]
+    fn test_call_task_uses_task_hints() {
+        // Two programs using the same hint string for different hints.
+        let hint_code = "memory[ap + 0] = segments.add()";
+        let alloc_segment = |offset| {
+            Hint::Core(CoreHintBase::Core(CoreHint::AllocSegment {
+                dst: CellRef {
+                    register: Register::AP,
+                    offset,
+                },
+            }))
+        };
+        let hints = [alloc_segment(0), alloc_segment(1)];
+
+        for hint in hints {
+            let task = cairo1_task(hint_code, hint.clone());
+            let hint_extension = run_call_task(task, 0).unwrap();
+
+            let task_hints = &hint_extension[&Relocatable::from((2, 0))];
+            assert_eq!(task_hints.len(), 1);
+            assert_eq!(task_hints[0].downcast_ref::<Hint>(), Some(&hint));
+        }
+    }
+
+    #[test]
+    fn test_call_task_invalid_hint() {
+        // The hint refers to a reference that the program does not define.
+        let hint_code = "ids.x = 1";
+        let program = program_with_hint(hint_code, HashMap::from([("x".to_string(), 0)]));
+        let task = TaskSpec::RunProgram(RunProgramTask::new(program, HashMap::new(), false));
+
+        let error = run_call_task(task, 3).unwrap_err().to_string();
+
+        assert!(error.contains("Task 3"), "{error}");
+        assert!(error.contains("pc 0:0"), "{error}");
+        assert!(error.contains(hint_code), "{error}");
+    }
+
     /// Creates a fake Program struct to act as a placeholder for the `BOOTLOADER_PROGRAM` variable.
     /// These other options have been considered:
     /// * a `HasIdentifiers` trait cannot be used as exec_scopes requires to cast to `Box<dyn Any>`,
@@ -935,7 +1176,13 @@ mod tests {
     }
 
     #[rstest]
-    fn test_call_cairo_pie_task(fibonacci_pie: CairoPie) {
+    #[case::labels(&[RET_PC_LABEL, CALL_TASK_LABEL], None)]
+    #[case::missing_label(&[CALL_TASK_LABEL], Some(RET_PC_LABEL))]
+    fn test_call_cairo_pie_task(
+        fibonacci_pie: CairoPie,
+        #[case] labels: &[&str],
+        #[case] missing_label: Option<&str>,
+    ) {
         let mut vm = vm!();
 
         // We set the program header pointer at (1, 0) and make it point to the start of segment #2.
@@ -967,12 +1214,11 @@ mod tests {
 
         let task = TaskSpec::CairoPieTask(CairoPieTask::new(fibonacci_pie, false));
         exec_scopes.insert_value(vars::TASK, task);
-        let bootloader_identifiers = HashMap::from(
-            [
-                ("starkware.cairo.bootloaders.simple_bootloader.execute_task.execute_task.ret_pc_label".to_string(), 10usize),
-                ("starkware.cairo.bootloaders.simple_bootloader.execute_task.execute_task.call_task".to_string(), 8usize)
-            ]
-        );
+        let label_pcs = HashMap::from([(RET_PC_LABEL, 10usize), (CALL_TASK_LABEL, 8usize)]);
+        let bootloader_identifiers = labels
+            .iter()
+            .map(|label| (label.to_string(), label_pcs[label]))
+            .collect();
         let program_identifiers = mock_program_identifiers(bootloader_identifiers);
         exec_scopes.insert_value(vars::PROGRAM_DATA_BASE, program_header_ptr);
         exec_scopes.insert_value(vars::BOOTLOADER_PROGRAM_IDENTIFIERS, program_identifiers);
@@ -982,16 +1228,135 @@ mod tests {
             .expect("Failed to load Cairo PIE task in the VM memory");
 
         let mut hint_processor = BootloaderHintProcessor::new();
+        let hint_data =
+            HintProcessorData::new_default(EXECUTE_TASK_CALL_TASK.to_string(), ids_data);
 
         // Execute it
-        call_task(
+        let result = call_task(
             &mut hint_processor,
             &mut vm,
             &mut exec_scopes,
-            &ids_data,
-            &ap_tracking,
+            &hint_data,
+            &HashMap::new(),
+        );
+        if let Some(missing_label) = missing_label {
+            assert_matches!(result, Err(HintError::CustomHint(message)) if message.contains(missing_label));
+            return;
+        }
+        result.expect("Hint failed unexpectedly");
+
+        // The task returns to `ret_pc_label`, stored after the builtins and the return fp in its
+        // execution segment.
+        let ret_pc = vm.get_relocatable((vm.get_ap() + 1).unwrap()).unwrap();
+        assert_eq!(ret_pc, (vm.get_pc() + 2).unwrap());
+    }
+
+    /// The task uses both modular arithmetic builtins, the other builtins keep their
+    /// pre-execution pointers.
+    #[rstest]
+    #[case::consistent(7, false)]
+    #[case::inconsistent(14, true)]
+    fn test_write_return_builtins_mod_builtins(
+        fibonacci_pie: CairoPie,
+        #[case] mul_mod_pie_size: usize,
+        #[case] expect_fail: bool,
+    ) {
+        let mut cairo_pie = fibonacci_pie;
+        let used_builtins = vec![BuiltinName::add_mod, BuiltinName::mul_mod];
+        cairo_pie.metadata.program.builtins = used_builtins.clone();
+        cairo_pie.metadata.builtin_segments = HashMap::from([
+            (BuiltinName::add_mod, (2, 7).into()),
+            (BuiltinName::mul_mod, (3, mul_mod_pie_size).into()),
+        ]);
+        let task: Box<dyn Task> = Box::new(CairoPieTask::new(cairo_pie, false));
+
+        // The pre-execution struct is at (1, 0), the return struct at (1, 11) and the builtin
+        // pointers returned by the task at (2, 0). Builtin i points to segment 3 + i.
+        let mut vm = vm!();
+        add_segments!(vm, 3 + ALL_BUILTINS.len());
+        let pre_execution_builtins_addr = Relocatable::from((1, 0));
+        let return_builtins_addr = Relocatable::from((1, ALL_BUILTINS.len()));
+        let used_builtins_addr = Relocatable::from((2, 0));
+        for index in 0..ALL_BUILTINS.len() {
+            vm.insert_value(
+                (pre_execution_builtins_addr + index).unwrap(),
+                Relocatable::from((3 + index as isize, 0)),
+            )
+            .unwrap();
+        }
+        let add_mod_index = ALL_BUILTINS.len() - 2;
+        let mul_mod_index = ALL_BUILTINS.len() - 1;
+        let add_mod_ptr = Relocatable::from((3 + add_mod_index as isize, 7));
+        let mul_mod_ptr = Relocatable::from((3 + mul_mod_index as isize, 7));
+        vm.insert_value(used_builtins_addr, add_mod_ptr).unwrap();
+        vm.insert_value((used_builtins_addr + 1).unwrap(), mul_mod_ptr)
+            .unwrap();
+
+        let result = write_return_builtins(
+            &mut vm,
+            return_builtins_addr,
+            &used_builtins,
+            used_builtins_addr,
+            pre_execution_builtins_addr,
+            &task,
+        );
+        if expect_fail {
+            assert_matches!(result, Err(HintError::AssertionFailed(_)));
+            return;
+        }
+        result.expect("Hint failed unexpectedly");
+
+        for (index, builtin) in ALL_BUILTINS.iter().enumerate() {
+            let expected = match builtin {
+                BuiltinName::add_mod => add_mod_ptr,
+                BuiltinName::mul_mod => mul_mod_ptr,
+                _ => Relocatable::from((3 + index as isize, 0)),
+            };
+            assert_eq!(
+                vm.get_relocatable((return_builtins_addr + index).unwrap()),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::matching_hash(false)]
+    #[case::wrong_hash(true)]
+    fn test_validate_hash_blake2s(fibonacci: Program, #[case] expect_fail: bool) {
+        let stripped_program = fibonacci.get_stripped_program().unwrap();
+        let program_hash = compute_program_hash_chain(
+            &stripped_program,
+            BOOTLOADER_VERSION,
+            ProgramHashFunction::Blake2s,
         )
-        .expect("Hint failed unexpectedly");
+        .unwrap();
+        let mut program_hash = field_element_to_felt(program_hash);
+        if expect_fail {
+            program_hash += Felt252::ONE;
+        }
+
+        let mut vm = vm!();
+        // output_ptr is at (1, 0) and points to (2, 0), the program hash is at output_ptr + 1
+        define_segments!(vm, 3, [((1, 0), (2, 0))]);
+        vm.insert_value(Relocatable::from((2, 1)), program_hash)
+            .unwrap();
+        vm.set_fp(1);
+        let ids_data = ids_data!["output_ptr"];
+
+        let mut exec_scopes = ExecutionScopes::new();
+        let task = TaskSpec::RunProgram(RunProgramTask::new(fibonacci, HashMap::new(), false));
+        exec_scopes.insert_box(vars::TASK, Box::new(task));
+
+        let hint_data = HintProcessorData::new_default(
+            EXECUTE_TASK_VALIDATE_HASH_BLAKE2S.to_string(),
+            ids_data,
+        );
+        let result = validate_hash_blake2s(&mut vm, &mut exec_scopes, &hint_data, &HashMap::new());
+        if expect_fail {
+            assert_matches!(result, Err(HintError::AssertionFailed(_)));
+        } else {
+            assert_matches!(result, Ok(()));
+        }
     }
 
     #[rstest]
@@ -11,13 +11,13 @@ use cairo_vm::vm::runners::builtin_runner::{OutputBuiltinRunner, OutputBuiltinSt
 use cairo_vm::vm::runners::cairo_pie::{
     BuiltinAdditionalData, OutputBuiltinAdditionalData, Pages, PublicMemoryPage,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::types::{PackedOutput, Task};
 
 use super::types::{CairoPieTask, RunProgramTask};
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FactTopology {
     #[allow(dead_code)]
     pub tree_structure: Vec<usize>,
@@ -29,6 +29,11 @@ struct FactTopologyFile<'a> {
     fact_topologies: Vec<&'a FactTopology>,
 }
 
+#[derive(Deserialize)]
+struct OwnedFactTopologyFile {
+    fact_topologies: Vec<FactTopology>,
+}
+
 impl AsRef<FactTopology> for FactTopology {
     fn as_ref(&self) -> &FactTopology {
         self
@@ -75,9 +80,6 @@ pub enum FactTopologyError {
     #[error("Expected {0} fact topologies but got {1}")]
     WrongNumberOfFactTopologies(usize, usize),
 
-    #[error("Composite packed outputs are not supported yet")]
-    CompositePackedOutputNotSupported(PackedOutput),
-
     #[error("Could not add page to output: {0}")]
     FailedToAddOutputPage(#[from] RunnerError),
 
@@ -114,15 +116,40 @@ impl From<WriteFactTopologiesError> for HintError {
     }
 }
 
+#[derive(thiserror_no_std::Error, Debug)]
+pub enum ReadFactTopologiesError {
+    #[error("Failed to open fact topology file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize fact topologies: {0}")]
+    Deserialization(#[from] serde_json::Error),
+}
+
 /// Flattens and extracts the fact topologies from packed outputs.
 ///
-/// Note that `packed_outputs` and `fact_topologies` must have the same length.
+/// Note that `packed_outputs` and `fact_topologies` must have the same length. The fact
+/// topology of a composite packed output is ignored and replaced by the plain fact topologies
+/// of its subtasks.
 ///
 /// * `packed_outputs`: Packed outputs.
 /// * `fact_topologies`: Fact topologies.
+///
+/// Reimplements the following Python code:
+/// ```no-run
+/// def compute_fact_topologies(packed_outputs, fact_topologies):
+///     plain_fact_topologies = []
+///     assert len(packed_outputs) == len(fact_topologies)
+///     for packed_output, fact_topology in zip(packed_outputs, fact_topologies):
+///         if isinstance(packed_output, PlainPackedOutput):
+///             plain_fact_topologies.append(fact_topology)
+///         elif isinstance(packed_output, CompositePackedOutput):
+///             plain_fact_topologies.extend(packed_output.get_plain_fact_topologies())
+///         else:
+///             raise NotImplementedError(...)
+///     return plain_fact_topologies
+/// ```
 pub fn compute_fact_topologies<'a>(
-    packed_outputs: &Vec<PackedOutput>,
-    fact_topologies: &'a Vec<FactTopology>,
+    packed_outputs: &'a [PackedOutput],
+    fact_topologies: &'a [FactTopology],
 ) -> Result<Vec<&'a FactTopology>, FactTopologyError> {
     if packed_outputs.len() != fact_topologies.len() {
         return Err(FactTopologyError::WrongNumberOfFactTopologies(
@@ -138,10 +165,8 @@ pub fn compute_fact_topologies<'a>(
             PackedOutput::Plain(_) => {
                 plain_fact_topologies.push(fact_topology);
             }
-            PackedOutput::Composite(_) => {
-                return Err(FactTopologyError::CompositePackedOutputNotSupported(
-                    packed_output.clone(),
-                ));
+            PackedOutput::Composite(composite_packed_output) => {
+                plain_fact_topologies.extend(composite_packed_output.get_plain_fact_topologies()?);
             }
         }
     }
@@ -440,6 +465,18 @@ pub fn write_to_fact_topologies_file<FT: AsRef<FactTopology>>(
     Ok(())
 }
 
+/// Reads fact topologies from a file written by `write_to_fact_topologies_file`.
+///
+/// * `path`: File path.
+pub fn read_fact_topologies_file(
+    path: &Path,
+) -> Result<Vec<FactTopology>, ReadFactTopologiesError> {
+    let file = File::open(path)?;
+    let fact_topology_file: OwnedFactTopologyFile =
+        serde_json::from_reader(std::io::BufReader::new(file))?;
+    Ok(fact_topology_file.fact_topologies)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -488,18 +525,88 @@ mod tests {
         }
     }
 
+    fn plain_topology(page_sizes: Vec<usize>) -> FactTopology {
+        FactTopology {
+            tree_structure: vec![1, 0],
+            page_sizes,
+        }
+    }
+
     #[test]
-    /// Composite outputs are not supported (yet).
+    /// The fact topology of a composite output is replaced by the ones of its subtasks.
     fn test_compute_fact_topologies_composite_output() {
-        let packed_outputs = vec![PackedOutput::Composite(CompositePackedOutput::default())];
-        let fact_topologies = vec![FactTopology {
-            tree_structure: vec![],
-            page_sizes: vec![],
-        }];
+        let packed_outputs = vec![
+            PackedOutput::Plain(vec![]),
+            PackedOutput::Composite(CompositePackedOutput {
+                outputs: vec![],
+                subtasks: vec![PackedOutput::Plain(vec![]), PackedOutput::Plain(vec![])],
+                fact_topologies: vec![plain_topology(vec![2]), plain_topology(vec![3])],
+            }),
+        ];
+        let fact_topologies = vec![plain_topology(vec![1]), plain_topology(vec![2])];
+
+        let plain_fact_topologies = compute_fact_topologies(&packed_outputs, &fact_topologies)
+            .expect("Failed to compute fact topologies");
+        assert_eq!(
+            plain_fact_topologies,
+            vec![
+                &plain_topology(vec![1]),
+                &plain_topology(vec![2]),
+                &plain_topology(vec![3])
+            ]
+        );
+    }
+
+    #[test]
+    /// Composite outputs are flattened recursively, in depth-first order.
+    fn test_compute_fact_topologies_nested_composite_output() {
+        let inner = PackedOutput::Composite(CompositePackedOutput {
+            outputs: vec![],
+            subtasks: vec![PackedOutput::Plain(vec![]), PackedOutput::Plain(vec![])],
+            fact_topologies: vec![plain_topology(vec![3]), plain_topology(vec![4])],
+        });
+        let outer = PackedOutput::Composite(CompositePackedOutput {
+            outputs: vec![],
+            subtasks: vec![
+                PackedOutput::Plain(vec![]),
+                inner,
+                PackedOutput::Plain(vec![]),
+            ],
+            fact_topologies: vec![
+                plain_topology(vec![2]),
+                plain_topology(vec![]),
+                plain_topology(vec![5]),
+            ],
+        });
+        let packed_outputs = vec![outer, PackedOutput::Plain(vec![])];
+        let fact_topologies = vec![plain_topology(vec![]), plain_topology(vec![6])];
+
+        let plain_fact_topologies = compute_fact_topologies(&packed_outputs, &fact_topologies)
+            .expect("Failed to compute fact topologies");
+        let page_sizes: Vec<_> = plain_fact_topologies
+            .iter()
+            .map(|fact_topology| fact_topology.page_sizes.clone())
+            .collect();
+        assert_eq!(
+            page_sizes,
+            vec![vec![2], vec![3], vec![4], vec![5], vec![6]]
+        );
+    }
+
+    #[test]
+    /// A composite output must have one fact topology per subtask.
+    fn test_compute_fact_topologies_composite_output_len_mismatch() {
+        let packed_outputs = vec![PackedOutput::Composite(CompositePackedOutput {
+            outputs: vec![],
+            subtasks: vec![PackedOutput::Plain(vec![]), PackedOutput::Plain(vec![])],
+            fact_topologies: vec![plain_topology(vec![1])],
+        })];
+        let fact_topologies = vec![plain_topology(vec![])];
+
         let result = compute_fact_topologies(&packed_outputs, &fact_topologies);
         assert!(matches!(
             result,
-            Err(FactTopologyError::CompositePackedOutputNotSupported(_))
+            Err(FactTopologyError::WrongNumberOfFactTopologies(2, 1))
         ));
     }
 
@@ -0,0 +1,616 @@
+use cairo_vm::Felt252;
+use serde::{Serialize, Serializer};
+use sha3::{Digest, Keccak256};
+use starknet_crypto::{poseidon_hash_many, FieldElement};
+
+use crate::bootloader_output::{BootloaderOutput, BootloaderOutputError};
+use crate::fact_topologies::FactTopology;
+
+/// Hash function used to compute facts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactHashFunction {
+    /// Keccak256 over 32-byte big-endian words, as done by the GPS verifier on L1.
+    Keccak,
+    /// `poseidon_hash_many` over field elements.
+    Poseidon,
+}
+
+/// A 256-bit hash, stored big-endian.
+///
+/// Keccak hashes do not necessarily fit in a field element, so facts are not represented as
+/// `Felt252`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FactHash(pub [u8; 32]);
+
+impl FactHash {
+    /// Returns `self + 1`, modulo 2^256 for Keccak and modulo the field prime for Poseidon.
+    fn increment(self, hash_function: FactHashFunction) -> Result<Self, FactError> {
+        match hash_function {
+            FactHashFunction::Keccak => {
+                let mut bytes = self.0;
+                for byte in bytes.iter_mut().rev() {
+                    let (value, overflow) = byte.overflowing_add(1);
+                    *byte = value;
+                    if !overflow {
+                        break;
+                    }
+                }
+                Ok(Self(bytes))
+            }
+            FactHashFunction::Poseidon => {
+                let value = self.to_field_element()? + FieldElement::ONE;
+                Ok(Self(value.to_bytes_be()))
+            }
+        }
+    }
+
+    fn to_field_element(self) -> Result<FieldElement, FactError> {
+        FieldElement::from_bytes_be(&self.0).map_err(|_| FactError::NotAFieldElement(self))
+    }
+}
+
+impl From<&Felt252> for FactHash {
+    fn from(value: &Felt252) -> Self {
+        Self(value.to_bytes_be())
+    }
+}
+
+impl From<usize> for FactHash {
+    fn from(value: usize) -> Self {
+        Self::from(&Felt252::from(value))
+    }
+}
+
+impl std::fmt::Display for FactHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl Serialize for FactHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(thiserror_no_std::Error, Debug)]
+pub enum FactError {
+    #[error(transparent)]
+    Output(#[from] BootloaderOutputError),
+
+    #[error("Expected {0} fact topologies but got {1}")]
+    WrongNumberOfFactTopologies(usize, usize),
+
+    #[error("Task {task_index}: fact topology covers {expected} words, but the task output has {actual}")]
+    OutputSizeMismatch {
+        task_index: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("Invalid tree structure: {0}")]
+    InvalidTreeStructure(&'static str),
+
+    #[error("Hash {0} is not a valid field element")]
+    NotAFieldElement(FactHash),
+}
+
+/// The fact of a task run by the bootloader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskFact {
+    pub program_hash: FactHash,
+    /// Root of the Merkle-like tree built over the task output pages.
+    pub output_root: FactHash,
+    /// `H(program_hash, output_root)`.
+    pub fact: FactHash,
+}
+
+/// Hashes a list of 256-bit words.
+fn hash_words(words: &[FactHash], hash_function: FactHashFunction) -> Result<FactHash, FactError> {
+    match hash_function {
+        FactHashFunction::Keccak => {
+            let mut hasher = Keccak256::new();
+            for word in words {
+                hasher.update(word.0);
+            }
+            Ok(FactHash(hasher.finalize().into()))
+        }
+        FactHashFunction::Poseidon => {
+            let elements = words
+                .iter()
+                .map(|word| word.to_field_element())
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(FactHash(poseidon_hash_many(&elements).to_bytes_be()))
+        }
+    }
+}
+
+/// A node of the fact tree.
+struct FactNode {
+    node_hash: FactHash,
+    end_offset: usize,
+}
+
+/// Computes the root of the tree described by `fact_topology` over the output of a program.
+///
+/// Reimplements the following Python code:
+/// ```no-run
+/// def generate_output_root(program_output, fact_topology):
+///     page_sizes = fact_topology.page_sizes
+///     tree_structure = fact_topology.tree_structure
+///     node_stack = []
+///     offset = 0
+///     page_index = 0
+///     assert len(tree_structure) % 2 == 0
+///     for n_pages, n_nodes in zip(tree_structure[::2], tree_structure[1::2]):
+///         # Push n_pages (pages) to the stack.
+///         for _ in range(n_pages):
+///             page_size = page_sizes[page_index]
+///             page_index += 1
+///             end_offset = offset + page_size
+///             node_stack.append(FactNode(
+///                 node_hash=keccak_ints(program_output[offset:end_offset]),
+///                 end_offset=end_offset, ...))
+///             offset = end_offset
+///         if n_nodes == 0:
+///             continue
+///         # Create a parent node to the last n_nodes in the head of the stack.
+///         child_nodes = node_stack[-n_nodes:]
+///         del node_stack[-n_nodes:]
+///         node_data = [x for node in child_nodes for x in [node.node_hash, node.end_offset]]
+///         node_stack.append(FactNode(
+///             # Add one to the hash to distinguish nodes from pages.
+///             node_hash=keccak_ints(node_data) + 1,
+///             end_offset=child_nodes[-1].end_offset, ...))
+///     assert len(node_stack) == 1
+///     assert page_index == len(page_sizes)
+///     assert offset == len(program_output)
+///     return node_stack[0]
+/// ```
+pub fn compute_output_root(
+    program_output: &[Felt252],
+    fact_topology: &FactTopology,
+    hash_function: FactHashFunction,
+) -> Result<FactHash, FactError> {
+    let tree_structure = &fact_topology.tree_structure;
+    let page_sizes = &fact_topology.page_sizes;
+
+    if tree_structure.len() % 2 != 0 {
+        return Err(FactError::InvalidTreeStructure(
+            "tree structure must have an even length",
+        ));
+    }
+
+    let mut node_stack: Vec<FactNode> = vec![];
+    let mut offset = 0;
+    let mut page_index = 0;
+
+    for pair in tree_structure.chunks_exact(2) {
+        let (n_pages, n_nodes) = (pair[0], pair[1]);
+
+        for _ in 0..n_pages {
+            let page_size = *page_sizes
+                .get(page_index)
+                .ok_or(FactError::InvalidTreeStructure("not enough pages"))?;
+            page_index += 1;
+            let end_offset = offset + page_size;
+            let page =
+                program_output
+                    .get(offset..end_offset)
+                    .ok_or(FactError::InvalidTreeStructure(
+                        "pages exceed the program output",
+                    ))?;
+            let page_words: Vec<FactHash> = page.iter().map(FactHash::from).collect();
+            node_stack.push(FactNode {
+                node_hash: hash_words(&page_words, hash_function)?,
+                end_offset,
+            });
+            offset = end_offset;
+        }
+
+        if n_nodes == 0 {
+            continue;
+        }
+        if n_nodes > node_stack.len() {
+            return Err(FactError::InvalidTreeStructure(
+                "not enough nodes in the stack",
+            ));
+        }
+
+        let child_nodes = node_stack.split_off(node_stack.len() - n_nodes);
+        let node_data: Vec<FactHash> = child_nodes
+            .iter()
+            .flat_map(|node| [node.node_hash, FactHash::from(node.end_offset)])
+            .collect();
+        node_stack.push(FactNode {
+            node_hash: hash_words(&node_data, hash_function)?.increment(hash_function)?,
+            end_offset: child_nodes[n_nodes - 1].end_offset,
+        });
+    }
+
+    if node_stack.len() != 1 {
+        return Err(FactError::InvalidTreeStructure(
+            "tree structure must reduce to a single root",
+        ));
+    }
+    if page_index != page_sizes.len() {
+        return Err(FactError::InvalidTreeStructure(
+            "tree structure does not use all the pages",
+        ));
+    }
+    if offset != program_output.len() {
+        return Err(FactError::InvalidTreeStructure(
+            "pages do not cover the whole program output",
+        ));
+    }
+
+    Ok(node_stack.remove(0).node_hash)
+}
+
+/// Computes the fact `H(program_hash, output_root)` of a program output.
+pub fn compute_fact(
+    program_hash: &Felt252,
+    program_output: &[Felt252],
+    fact_topology: &FactTopology,
+    hash_function: FactHashFunction,
+) -> Result<TaskFact, FactError> {
+    let program_hash = FactHash::from(program_hash);
+    let output_root = compute_output_root(program_output, fact_topology, hash_function)?;
+    let fact = hash_words(&[program_hash, output_root], hash_function)?;
+
+    Ok(TaskFact {
+        program_hash,
+        output_root,
+        fact,
+    })
+}
+
+/// Fact topology of an output written to a single page, used when the program did not specify
+/// any.
+pub fn single_page_fact_topology(output_size: usize) -> FactTopology {
+    FactTopology {
+        tree_structure: vec![1, 0],
+        page_sizes: vec![output_size],
+    }
+}
+
+/// Computes the fact of every task in the output of the (simple) bootloader.
+///
+/// The output is decoded with `BootloaderOutput::decode`.
+///
+/// * `bootloader_output`: Content of the output segment of the bootloader.
+/// * `fact_topologies`: Plain fact topologies of the tasks, as written to the fact topologies
+///   file. If `None`, the output of each task is assumed to be a single page.
+/// * `hash_function`: Hash function to use.
+pub fn compute_task_facts(
+    bootloader_output: &[Felt252],
+    fact_topologies: Option<&[FactTopology]>,
+    hash_function: FactHashFunction,
+) -> Result<Vec<TaskFact>, FactError> {
+    let tasks = BootloaderOutput::decode(bootloader_output)?.tasks;
+
+    if let Some(fact_topologies) = fact_topologies {
+        if fact_topologies.len() != tasks.len() {
+            return Err(FactError::WrongNumberOfFactTopologies(
+                tasks.len(),
+                fact_topologies.len(),
+            ));
+        }
+    }
+
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(task_index, task)| {
+            let program_output = task.output();
+            let fact_topology = match fact_topologies {
+                Some(fact_topologies) => fact_topologies[task_index].clone(),
+                None => single_page_fact_topology(program_output.len()),
+            };
+            let topology_size: usize = fact_topology.page_sizes.iter().sum();
+            if topology_size != program_output.len() {
+                return Err(FactError::OutputSizeMismatch {
+                    task_index,
+                    expected: topology_size,
+                    actual: program_output.len(),
+                });
+            }
+
+            compute_fact(
+                task.program_hash(),
+                &program_output,
+                &fact_topology,
+                hash_function,
+            )
+        })
+        .collect()
+}
+
+/// The facts of a bootloader run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BootloaderFacts {
+    /// Fact of the bootloader program over its whole output, as a single page. With Poseidon,
+    /// its output root is the output hash reported by a Cairo verifier for a proof of the run.
+    pub bootloader: TaskFact,
+    /// Facts of the tasks, as registered by the GPS verifier.
+    pub tasks: Vec<TaskFact>,
+}
+
+/// Computes the fact of the whole output of the (simple) bootloader along with the facts of its
+/// tasks. See `compute_task_facts` for the arguments.
+///
+/// * `bootloader_program_hash`: Hash of the bootloader program that produced the output.
+pub fn compute_bootloader_facts(
+    bootloader_program_hash: &Felt252,
+    bootloader_output: &[Felt252],
+    fact_topologies: Option<&[FactTopology]>,
+    hash_function: FactHashFunction,
+) -> Result<BootloaderFacts, FactError> {
+    let tasks = compute_task_facts(bootloader_output, fact_topologies, hash_function)?;
+    let bootloader = compute_fact(
+        bootloader_program_hash,
+        bootloader_output,
+        &single_page_fact_topology(bootloader_output.len()),
+        hash_function,
+    )?;
+
+    Ok(BootloaderFacts { bootloader, tasks })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn keccak_words(words: &[Felt252]) -> FactHash {
+        let mut hasher = Keccak256::new();
+        for word in words {
+            hasher.update(word.to_bytes_be());
+        }
+        FactHash(hasher.finalize().into())
+    }
+
+    fn felts(values: &[u64]) -> Vec<Felt252> {
+        values.iter().copied().map(Felt252::from).collect()
+    }
+
+    #[test]
+    fn test_keccak_of_empty_page() {
+        let root =
+            compute_output_root(&[], &single_page_fact_topology(0), FactHashFunction::Keccak)
+                .unwrap();
+        assert_eq!(
+            root.to_string(),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[rstest]
+    #[case::keccak(FactHashFunction::Keccak)]
+    #[case::poseidon(FactHashFunction::Poseidon)]
+    fn test_single_page_root(#[case] hash_function: FactHashFunction) {
+        let output = felts(&[1, 2, 3]);
+        let root =
+            compute_output_root(&output, &single_page_fact_topology(3), hash_function).unwrap();
+        let expected = hash_words(
+            &output.iter().map(FactHash::from).collect::<Vec<_>>(),
+            hash_function,
+        )
+        .unwrap();
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn test_two_pages_root() {
+        let output = felts(&[1, 2, 3, 4, 5]);
+        let fact_topology = FactTopology {
+            tree_structure: vec![2, 2],
+            page_sizes: vec![2, 3],
+        };
+        let root = compute_output_root(&output, &fact_topology, FactHashFunction::Keccak).unwrap();
+
+        let page_1 = keccak_words(&output[0..2]);
+        let page_2 = keccak_words(&output[2..5]);
+        let expected = hash_words(
+            &[page_1, FactHash::from(2), page_2, FactHash::from(5)],
+            FactHashFunction::Keccak,
+        )
+        .unwrap()
+        .increment(FactHashFunction::Keccak)
+        .unwrap();
+        assert_eq!(root, expected);
+    }
+
+    #[rstest]
+    #[case::odd_length(vec![1], vec![3])]
+    #[case::not_enough_pages(vec![2, 1], vec![3])]
+    #[case::unused_pages(vec![1, 0], vec![1, 2])]
+    #[case::several_roots(vec![2, 0], vec![1, 2])]
+    #[case::not_covering_output(vec![1, 0], vec![2])]
+    fn test_invalid_tree_structure(
+        #[case] tree_structure: Vec<usize>,
+        #[case] page_sizes: Vec<usize>,
+    ) {
+        let fact_topology = FactTopology {
+            tree_structure,
+            page_sizes,
+        };
+        let result =
+            compute_output_root(&felts(&[1, 2, 3]), &fact_topology, FactHashFunction::Keccak);
+        assert!(matches!(result, Err(FactError::InvalidTreeStructure(_))));
+    }
+
+    #[test]
+    fn test_increment_carries() {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 0xff;
+        bytes[30] = 0xff;
+        let incremented = FactHash(bytes).increment(FactHashFunction::Keccak).unwrap();
+        let mut expected = [0u8; 32];
+        expected[29] = 1;
+        assert_eq!(incremented, FactHash(expected));
+    }
+
+    #[test]
+    fn test_compute_task_facts() {
+        // Two tasks with program hashes 0x10 and 0x20.
+        let output = felts(&[2, 4, 0x10, 7, 8, 3, 0x20, 9]);
+
+        let facts = compute_task_facts(&output, None, FactHashFunction::Keccak).unwrap();
+
+        assert_eq!(facts.len(), 2);
+        let output_root = keccak_words(&felts(&[7, 8]));
+        assert_eq!(facts[0].output_root, output_root);
+        assert_eq!(
+            facts[0].fact,
+            hash_words(
+                &[FactHash::from(0x10), output_root],
+                FactHashFunction::Keccak
+            )
+            .unwrap()
+        );
+        assert_eq!(facts[1].program_hash, FactHash::from(0x20));
+        assert_eq!(facts[1].output_root, keccak_words(&felts(&[9])));
+    }
+
+    #[test]
+    fn test_compute_task_facts_with_fact_topologies() {
+        let output = felts(&[1, 5, 0x10, 7, 8, 9]);
+        let fact_topologies = vec![FactTopology {
+            tree_structure: vec![2, 2],
+            page_sizes: vec![1, 2],
+        }];
+
+        let facts = compute_task_facts(&output, Some(&fact_topologies), FactHashFunction::Poseidon)
+            .unwrap();
+
+        let expected = compute_fact(
+            &Felt252::from(0x10),
+            &output[3..],
+            &fact_topologies[0],
+            FactHashFunction::Poseidon,
+        )
+        .unwrap();
+        assert_eq!(facts, vec![expected]);
+    }
+
+    #[rstest]
+    #[case::task_out_of_bounds(felts(&[1, 5, 0x10, 7]))]
+    #[case::trailing_output(felts(&[1, 3, 0x10, 7, 8]))]
+    #[case::invalid_size(felts(&[1, 1, 0x10]))]
+    fn test_compute_task_facts_invalid_output(#[case] output: Vec<Felt252>) {
+        assert!(compute_task_facts(&output, None, FactHashFunction::Keccak).is_err());
+    }
+    // The expected hashes of the tests below are printed by `scripts/fact_vectors.py`, which runs
+    // `generate_output_root` and `generate_program_fact` as written in cairo-lang's
+    // `compute_fact.py`, over a pure-Python Keccak-256.
+
+    #[rstest]
+    #[case::two_pages(
+        vec![2, 2],
+        vec![2, 3],
+        felts(&[1, 2, 3, 4, 5]),
+        "0xa4fe4d8844193225a879b6cd58a0e1924e8ae9ef6b6f304a5e46a739b10e2057",
+        "0x55bf28bd1a3720a1873a957cac8dad9a3f29dafd2db99bfa4477bbe73f937074"
+    )]
+    #[case::nested_nodes(
+        vec![2, 0, 2, 2, 0, 3],
+        vec![1, 2, 2, 1],
+        felts(&[1, 2, 3, 4, 5, 6]),
+        "0x44952773630512fb42170d45d6b72f37e7b517cc6e289e6237e555f94b3dbe17",
+        "0x9cede4ab07740a0f1e12db8d0ca83ff2bf5df6dee0c3b183c9bc199e1aa9d0ad"
+    )]
+    fn test_compute_fact_golden(
+        #[case] tree_structure: Vec<usize>,
+        #[case] page_sizes: Vec<usize>,
+        #[case] output: Vec<Felt252>,
+        #[case] expected_output_root: &str,
+        #[case] expected_fact: &str,
+    ) {
+        let fact_topology = FactTopology {
+            tree_structure,
+            page_sizes,
+        };
+
+        let fact = compute_fact(
+            &Felt252::from(0x1234),
+            &output,
+            &fact_topology,
+            FactHashFunction::Keccak,
+        )
+        .unwrap();
+
+        assert_eq!(fact.output_root.to_string(), expected_output_root);
+        assert_eq!(fact.fact.to_string(), expected_fact);
+    }
+
+    #[test]
+    fn test_compute_bootloader_facts_golden() {
+        // Two tasks with program hashes 0xaa and 0xbb, the first one writing two pages.
+        let output = felts(&[2, 7, 0xaa, 1, 2, 3, 4, 5, 3, 0xbb, 6]);
+        let fact_topologies = vec![
+            FactTopology {
+                tree_structure: vec![2, 2],
+                page_sizes: vec![2, 3],
+            },
+            single_page_fact_topology(1),
+        ];
+
+        let facts = compute_bootloader_facts(
+            &Felt252::from(0xb007),
+            &output,
+            Some(&fact_topologies),
+            FactHashFunction::Keccak,
+        )
+        .unwrap();
+
+        let to_strings = |fact: &TaskFact| (fact.output_root.to_string(), fact.fact.to_string());
+        assert_eq!(
+            to_strings(&facts.bootloader),
+            (
+                "0xe84bb9386338d35332edb55714f6e67115a8aa562c74aaa41b94a36d24090873".to_string(),
+                "0x61274871f31a0baa794d42670b44cd255e32aa6439c6b6874aaf2338e5b6cfbe".to_string()
+            )
+        );
+        assert_eq!(
+            facts.tasks.iter().map(to_strings).collect::<Vec<_>>(),
+            vec![
+                (
+                    "0xa4fe4d8844193225a879b6cd58a0e1924e8ae9ef6b6f304a5e46a739b10e2057"
+                        .to_string(),
+                    "0x3f806aa2d75124c46692b10da5ddc43f97ad302905484135dd4966ae367a0afe"
+                        .to_string()
+                ),
+                (
+                    "0xf652222313e28459528d920b65115c16c04f3efc82aaedc97be59f3f377c0d3f"
+                        .to_string(),
+                    "0x58790e063d6b5e4e3618dceef40a6745999eba24d2e4d9f27249d7613def82d0"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bootloader_fact_poseidon_is_output_hash() {
+        let output = felts(&[1, 3, 0x10, 7]);
+
+        let facts = compute_bootloader_facts(
+            &Felt252::from(0xb007),
+            &output,
+            None,
+            FactHashFunction::Poseidon,
+        )
+        .unwrap();
+
+        let elements: Vec<FieldElement> = [1u64, 3, 0x10, 7]
+            .into_iter()
+            .map(FieldElement::from)
+            .collect();
+        assert_eq!(
+            facts.bootloader.output_root,
+            FactHash(poseidon_hash_many(&elements).to_bytes_be())
+        );
+    }
+}
@@ -1,4 +1,3 @@
-use cairo_lang_casm::hints::Hint;
 use cairo_vm::{
     hint_processor::{
         builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData,
@@ -12,9 +11,10 @@ use std::collections::HashMap;
 
 pub use types::{
     BootloaderConfig, BootloaderInput, CairoPiePath, CairoPieTask, PackedOutput, RunProgramTask,
-    SimpleBootloaderInput, Task, TaskSpec,
+    SimpleBootloaderInput, Task, TaskSpec, UserArg,
 };
 
+pub use debug::{get_debug_hints, DebugHint, DebugMessage, DebugSink, LogLevel};
 pub use vars::BOOTLOADER_INPUT;
 
 use crate::{
@@ -36,16 +36,17 @@ use crate::{
         BOOTLOADER_SET_PACKED_OUTPUT_TO_SUBTASKS, EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT,
         EXECUTE_TASK_APPEND_FACT_TOPOLOGIES, EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS,
         EXECUTE_TASK_CALL_TASK, EXECUTE_TASK_EXIT_SCOPE, EXECUTE_TASK_LOAD_PROGRAM,
-        EXECUTE_TASK_VALIDATE_HASH_V0_13_0, EXECUTE_TASK_VALIDATE_HASH_V0_13_1,
-        EXECUTE_TASK_WRITE_RETURN_BUILTINS, INNER_SELECT_BUILTINS_SELECT_BUILTIN,
-        SELECT_BUILTINS_ENTER_SCOPE, SIMPLE_BOOTLOADER_DIVIDE_NUM_BY_2,
-        SIMPLE_BOOTLOADER_PREPARE_TASK_RANGE_CHECKS, SIMPLE_BOOTLOADER_SET_CURRENT_TASK,
-        SIMPLE_BOOTLOADER_SET_TASKS_VARIABLE, SIMPLE_BOOTLOADER_ZERO_V0_13_0,
-        SIMPLE_BOOTLOADER_ZERO_V0_13_1,
+        EXECUTE_TASK_VALIDATE_HASH_BLAKE2S, EXECUTE_TASK_VALIDATE_HASH_V0_13_0,
+        EXECUTE_TASK_VALIDATE_HASH_V0_13_1, EXECUTE_TASK_WRITE_RETURN_BUILTINS,
+        INNER_SELECT_BUILTINS_SELECT_BUILTIN, SELECT_BUILTINS_ENTER_SCOPE,
+        SIMPLE_BOOTLOADER_DIVIDE_NUM_BY_2, SIMPLE_BOOTLOADER_PREPARE_TASK_RANGE_CHECKS,
+        SIMPLE_BOOTLOADER_SET_CURRENT_TASK, SIMPLE_BOOTLOADER_SET_TASKS_VARIABLE,
+        SIMPLE_BOOTLOADER_ZERO_V0_13_0, SIMPLE_BOOTLOADER_ZERO_V0_13_1,
     },
     execute_task_hints::{
         allocate_program_data_segment, append_fact_topologies, call_task, exit_scope_with_comments,
-        load_program_hint, validate_hash, write_return_builtins_hint,
+        load_program_hint, validate_hash, validate_hash_blake2s, validate_hash_v0_13_0,
+        write_return_builtins_hint,
     },
     inner_select_builtins::select_builtin,
     select_builtins::select_builtins_enter_scope,
@@ -56,9 +57,12 @@ use crate::{
 };
 
 pub mod bootloader_hints;
+pub mod bootloader_output;
 pub mod codes;
+pub mod debug;
 pub mod execute_task_hints;
 pub mod fact_topologies;
+pub mod facts;
 pub mod hint_processors;
 pub mod inner_select_builtins;
 pub mod load_cairo_pie;
@@ -68,7 +72,6 @@ pub mod select_builtins;
 pub mod simple_bootloader_hints;
 pub mod types;
 pub mod vars;
-pub mod debug;
 
 pub type HintImpl = fn(
     &mut VirtualMachine,
@@ -79,7 +82,6 @@ pub type HintImpl = fn(
 
 pub type ExtensiveHintImpl = fn(
     &mut dyn HintProcessor,
-    &HashMap<String, Hint>,
     &mut VirtualMachine,
     &mut ExecutionScopes,
     &HintProcessorData,
@@ -88,23 +90,6 @@ pub type ExtensiveHintImpl = fn(
 
 pub fn get_hints() -> HashMap<String, HintImpl> {
     let mut hints = HashMap::<String, HintImpl>::new();
-    hints.insert(debug::PRINT_FELT_HEX.into(), debug::print_felt_hex);
-    hints.insert(debug::PRINT_FELT.into(), debug::print_felt);
-    hints.insert(debug::PRINT_STRING.into(), debug::print_string);
-    hints.insert(debug::PRINT_UINT256.into(), debug::print_uint256);
-    hints.insert(debug::PRINT_UINT384.into(), debug::print_uint384);
-
-    hints.insert(debug::INFO_FELT.into(), debug::info_felt);
-    hints.insert(debug::INFO_FELT_HEX.into(), debug::info_felt_hex);
-    hints.insert(debug::INFO_STRING.into(), debug::info_string);
-    hints.insert(debug::INFO_UINT256.into(), debug::info_uint256);
-    hints.insert(debug::INFO_UINT384.into(), debug::info_uint384);
-
-    hints.insert(debug::DEBUG_FELT.into(), debug::debug_felt);
-    hints.insert(debug::DEBUG_FELT_HEX.into(), debug::debug_felt_hex);
-    hints.insert(debug::DEBUG_STRING.into(), debug::debug_string);
-    hints.insert(debug::DEBUG_UINT256.into(), debug::debug_uint256);
-    hints.insert(debug::DEBUG_UINT384.into(), debug::debug_uint384);
     hints.insert(
         BOOTLOADER_RESTORE_BOOTLOADER_OUTPUT.into(),
         restore_bootloader_output,
@@ -168,8 +153,15 @@ pub fn get_hints() -> HashMap<String, HintImpl> {
         allocate_program_data_segment,
     );
     hints.insert(EXECUTE_TASK_LOAD_PROGRAM.into(), load_program_hint);
-    hints.insert(EXECUTE_TASK_VALIDATE_HASH_V0_13_0.into(), validate_hash);
+    hints.insert(
+        EXECUTE_TASK_VALIDATE_HASH_V0_13_0.into(),
+        validate_hash_v0_13_0,
+    );
     hints.insert(EXECUTE_TASK_VALIDATE_HASH_V0_13_1.into(), validate_hash);
+    hints.insert(
+        EXECUTE_TASK_VALIDATE_HASH_BLAKE2S.into(),
+        validate_hash_blake2s,
+    );
     hints.insert(
         EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS.into(),
         assert_program_address,
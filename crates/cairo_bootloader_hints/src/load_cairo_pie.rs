@@ -34,13 +34,19 @@ pub enum SignatureRelocationError {
     #[error("The PIE requires ECDSA but the VM is not configured to use it")]
     EcdsaBuiltinNotFound,
 
-    #[error("Relocated signature data ({0} not on signature builtin segment {1}")]
+    #[error("Relocated signature data ({0}) not on signature builtin segment {1}")]
     RelocatedDataNotOnBuiltinSegment(Relocatable, isize),
 
     #[error("The Cairo PIE ECDSA builtin data is not in the expected format")]
     InvalidCairoPieEcdsaBuiltinData,
 }
 
+#[derive(Error, Debug)]
+pub enum ModBuiltinDataError {
+    #[error("The Cairo PIE {0} builtin data is not in the expected format")]
+    InvalidCairoPieModBuiltinData(BuiltinName),
+}
+
 #[derive(Error, Debug)]
 pub enum MemoryRelocationError {
     #[error(transparent)]
@@ -55,6 +61,9 @@ pub enum CairoPieLoaderError {
     #[error("Error while relocating signature builtin data: {0}")]
     SignatureRelocation(#[from] SignatureRelocationError),
 
+    #[error("Invalid modular arithmetic builtin data: {0}")]
+    ModBuiltinData(#[from] ModBuiltinDataError),
+
     #[error("Error while relocating Cairo PIE memory: {0}")]
     MemoryRelocationError(#[from] MemoryRelocationError),
 }
@@ -225,7 +234,7 @@ fn extend_additional_data(
                 builtin_segment_base,
             ));
         }
-        builtin.add_signature(*addr, signature)?;
+        builtin.add_signature(relocated_addr, signature)?;
     }
 
     Ok(())
@@ -254,6 +263,27 @@ fn relocate_builtin_additional_data(
     Ok(())
 }
 
+/// Checks the additional data of the modular arithmetic builtins.
+///
+/// Unlike the signature builtin, `add_mod` and `mul_mod` keep no data outside of their memory
+/// segments: their instances (including the `values_ptr` and `offsets_ptr` pointers) are moved
+/// to the bootloader builtin segments by the memory relocation, through the relocation entries
+/// of the initial stack.
+fn check_mod_builtin_additional_data(cairo_pie: &CairoPie) -> Result<(), ModBuiltinDataError> {
+    for builtin_name in [BuiltinName::add_mod, BuiltinName::mul_mod] {
+        match cairo_pie.additional_data.0.get(&builtin_name) {
+            None | Some(BuiltinAdditionalData::None) | Some(BuiltinAdditionalData::Empty(_)) => {}
+            Some(_) => {
+                return Err(ModBuiltinDataError::InvalidCairoPieModBuiltinData(
+                    builtin_name,
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Relocates the memory of the PIE.
 ///
 /// * `cairo_pie`: Cairo PIE.
@@ -296,7 +326,8 @@ pub(crate) fn load_cairo_pie(
         ret_pc,
     )?;
 
-    //relocate_builtin_additional_data(cairo_pie, vm, &relocation_table)?;
+    relocate_builtin_additional_data(cairo_pie, vm, &relocation_table)?;
+    check_mod_builtin_additional_data(cairo_pie)?;
     relocate_cairo_pie_memory(cairo_pie, vm, &relocation_table)?;
 
     Ok(())
@@ -305,8 +336,299 @@ pub(crate) fn load_cairo_pie(
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
+    use cairo_vm::types::layout_name::LayoutName;
+    use cairo_vm::types::program::Program;
+    use cairo_vm::vm::runners::cairo_pie::{
+        CairoPieAdditionalData, CairoPieMetadata, CairoPieVersion, SegmentInfo, StrippedProgram,
+    };
+    use cairo_vm::vm::runners::cairo_runner::{CairoRunner, ExecutionResources};
+    use starknet_crypto::{get_public_key, sign, FieldElement};
 
     use super::*;
+    use crate::execute_task_hints::field_element_to_felt;
+
+    /// An `add_mod` or `mul_mod` instance: the modulus words `p0..p3`, `values_ptr`,
+    /// `offsets_ptr` and `n`. The values and offsets tables live in the execution segment.
+    fn mod_builtin_instance(
+        segment_index: usize,
+        p0: u64,
+    ) -> Vec<((usize, usize), MaybeRelocatable)> {
+        vec![
+            ((segment_index, 0), Felt252::from(p0).into()),
+            ((segment_index, 1), Felt252::ZERO.into()),
+            ((segment_index, 2), Felt252::ZERO.into()),
+            ((segment_index, 3), Felt252::ZERO.into()),
+            ((segment_index, 4), Relocatable::from((1, 4)).into()),
+            ((segment_index, 5), Relocatable::from((1, 16)).into()),
+            ((segment_index, 6), Felt252::ONE.into()),
+        ]
+    }
+
+    /// A PIE of a program using both modular arithmetic builtins, laid out by hand:
+    /// segment 0 is the program, 1 the execution segment, 2 and 3 the `add_mod` and `mul_mod`
+    /// segments, 4 and 5 the return fp and pc segments.
+    fn mod_builtins_pie() -> CairoPie {
+        let program_data: Vec<MaybeRelocatable> = vec![
+            Felt252::from(0x208b7fff7fff7ffeu64).into(),
+            Felt252::from(0x208b7fff7fff7ffeu64).into(),
+        ];
+        let mut memory: Vec<((usize, usize), MaybeRelocatable)> = program_data
+            .iter()
+            .enumerate()
+            .map(|(offset, value)| ((0, offset), value.clone()))
+            .collect();
+        memory.extend([
+            ((1, 0), Relocatable::from((2, 0)).into()),
+            ((1, 1), Relocatable::from((3, 0)).into()),
+            ((1, 2), Relocatable::from((4, 0)).into()),
+            ((1, 3), Relocatable::from((5, 0)).into()),
+        ]);
+        memory.extend(mod_builtin_instance(2, 7));
+        memory.extend(mod_builtin_instance(3, 11));
+
+        CairoPie {
+            metadata: CairoPieMetadata {
+                program: StrippedProgram {
+                    data: program_data,
+                    builtins: vec![BuiltinName::add_mod, BuiltinName::mul_mod],
+                    main: 0,
+                    prime: (),
+                },
+                program_segment: SegmentInfo::from((0, 2)),
+                execution_segment: SegmentInfo::from((1, 24)),
+                ret_fp_segment: SegmentInfo::from((4, 0)),
+                ret_pc_segment: SegmentInfo::from((5, 0)),
+                builtin_segments: HashMap::from([
+                    (BuiltinName::add_mod, SegmentInfo::from((2, 7))),
+                    (BuiltinName::mul_mod, SegmentInfo::from((3, 7))),
+                ]),
+                extra_segments: vec![],
+            },
+            memory: CairoPieMemory(memory),
+            execution_resources: ExecutionResources::default(),
+            additional_data: CairoPieAdditionalData(HashMap::from([
+                (BuiltinName::add_mod, BuiltinAdditionalData::None),
+                (BuiltinName::mul_mod, BuiltinAdditionalData::None),
+            ])),
+            version: CairoPieVersion { cairo_pie: () },
+        }
+    }
+
+    /// A program that only declares the ECDSA builtin, to get a VM with a signature builtin.
+    const ECDSA_PROGRAM: &str = r#"{
+        "attributes": [],
+        "builtins": ["ecdsa"],
+        "data": ["0x208b7fff7fff7ffe"],
+        "debug_info": null,
+        "hints": {},
+        "identifiers": {"__main__.main": {"decorators": [], "pc": 0, "type": "function"}},
+        "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+        "reference_manager": {"references": []}
+    }"#;
+
+    /// A runner whose VM has the signature builtin, with its validation rule.
+    fn ecdsa_runner() -> CairoRunner {
+        let program = Program::from_bytes(ECDSA_PROGRAM.as_bytes(), Some("main")).unwrap();
+        let mut runner =
+            CairoRunner::new(&program, LayoutName::all_cairo, None, false, false, false).unwrap();
+        runner.initialize(false).unwrap();
+        runner
+    }
+
+    /// A PIE of a program that verified one signature, laid out by hand: segment 0 is the
+    /// program, 1 the execution segment, 2 the ECDSA segment, 3 and 4 the return fp and pc
+    /// segments.
+    fn ecdsa_pie(message: Felt252, signature: (Felt252, Felt252), public_key: Felt252) -> CairoPie {
+        let program_data: Vec<MaybeRelocatable> = vec![Felt252::from(0x208b7fff7fff7ffeu64).into()];
+        let memory = vec![
+            ((0, 0), program_data[0].clone()),
+            ((1, 0), Relocatable::from((2, 0)).into()),
+            ((1, 1), Relocatable::from((3, 0)).into()),
+            ((1, 2), Relocatable::from((4, 0)).into()),
+            ((2, 0), public_key.into()),
+            ((2, 1), message.into()),
+        ];
+
+        CairoPie {
+            metadata: CairoPieMetadata {
+                program: StrippedProgram {
+                    data: program_data,
+                    builtins: vec![BuiltinName::ecdsa],
+                    main: 0,
+                    prime: (),
+                },
+                program_segment: SegmentInfo::from((0, 1)),
+                execution_segment: SegmentInfo::from((1, 3)),
+                ret_fp_segment: SegmentInfo::from((3, 0)),
+                ret_pc_segment: SegmentInfo::from((4, 0)),
+                builtin_segments: HashMap::from([(BuiltinName::ecdsa, SegmentInfo::from((2, 2)))]),
+                extra_segments: vec![],
+            },
+            memory: CairoPieMemory(memory),
+            execution_resources: ExecutionResources::default(),
+            additional_data: CairoPieAdditionalData(HashMap::from([(
+                BuiltinName::ecdsa,
+                BuiltinAdditionalData::Signature(
+                    [(Relocatable::from((2, 0)), signature)]
+                        .into_iter()
+                        .collect(),
+                ),
+            )])),
+            version: CairoPieVersion { cairo_pie: () },
+        }
+    }
+
+    #[test]
+    fn test_load_cairo_pie_with_signatures() {
+        let private_key = FieldElement::from(0x1234u64);
+        let message = FieldElement::from(0x5678u64);
+        let signature = sign(&private_key, &message, &FieldElement::TWO).unwrap();
+        let cairo_pie = ecdsa_pie(
+            field_element_to_felt(message),
+            (
+                field_element_to_felt(signature.r),
+                field_element_to_felt(signature.s),
+            ),
+            field_element_to_felt(get_public_key(&private_key)),
+        );
+
+        let mut runner = ecdsa_runner();
+        let vm = &mut runner.vm;
+
+        // A previous task already used one instance of the signature builtin.
+        let ecdsa_base = vm.get_signature_builtin().unwrap().base() as isize;
+        let ecdsa_ptr = Relocatable::from((ecdsa_base, 2));
+        let program_address = vm.add_memory_segment();
+        let execution_segment_address = vm.add_memory_segment();
+        vm.insert_value(execution_segment_address, ecdsa_ptr)
+            .unwrap();
+        let ret_fp = vm.add_memory_segment();
+        let ret_pc = Relocatable::from((0, 1));
+
+        load_cairo_pie(
+            &cairo_pie,
+            vm,
+            program_address,
+            execution_segment_address,
+            ret_fp,
+            ret_pc,
+        )
+        .expect("Failed to load the Cairo PIE");
+
+        assert_eq!(
+            vm.get_integer(ecdsa_ptr).unwrap().into_owned(),
+            field_element_to_felt(get_public_key(&private_key))
+        );
+        assert_eq!(
+            vm.get_integer((ecdsa_ptr + 1).unwrap())
+                .unwrap()
+                .into_owned(),
+            field_element_to_felt(message)
+        );
+    }
+
+    #[test]
+    fn test_load_cairo_pie_with_invalid_signature() {
+        let private_key = FieldElement::from(0x1234u64);
+        let message = FieldElement::from(0x5678u64);
+        let signature = sign(&private_key, &message, &FieldElement::TWO).unwrap();
+        // Sign another message than the one written in memory.
+        let cairo_pie = ecdsa_pie(
+            field_element_to_felt(message) + Felt252::ONE,
+            (
+                field_element_to_felt(signature.r),
+                field_element_to_felt(signature.s),
+            ),
+            field_element_to_felt(get_public_key(&private_key)),
+        );
+
+        let mut runner = ecdsa_runner();
+        let vm = &mut runner.vm;
+
+        let ecdsa_base = vm.get_signature_builtin().unwrap().base() as isize;
+        let program_address = vm.add_memory_segment();
+        let execution_segment_address = vm.add_memory_segment();
+        vm.insert_value(
+            execution_segment_address,
+            Relocatable::from((ecdsa_base, 0)),
+        )
+        .unwrap();
+        let ret_fp = vm.add_memory_segment();
+
+        let result = load_cairo_pie(
+            &cairo_pie,
+            vm,
+            program_address,
+            execution_segment_address,
+            ret_fp,
+            Relocatable::from((0, 1)),
+        );
+        assert_matches!(
+            result,
+            Err(CairoPieLoaderError::MemoryRelocationError(
+                MemoryRelocationError::Memory(MemoryError::InvalidSignature(_))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_load_cairo_pie_with_mod_builtins() {
+        let cairo_pie = mod_builtins_pie();
+        let mut vm = VirtualMachine::new(false, false);
+        for _ in 0..5 {
+            vm.add_memory_segment();
+        }
+
+        // The bootloader passes its own add_mod and mul_mod pointers to the task, at the start
+        // of the execution segment of the task.
+        let program_address = Relocatable::from((0, 0));
+        let execution_segment_address = Relocatable::from((1, 10));
+        let add_mod_ptr = Relocatable::from((2, 14));
+        let mul_mod_ptr = Relocatable::from((3, 7));
+        vm.insert_value(execution_segment_address, add_mod_ptr)
+            .unwrap();
+        vm.insert_value((execution_segment_address + 1).unwrap(), mul_mod_ptr)
+            .unwrap();
+        let ret_fp = Relocatable::from((4, 0));
+        let ret_pc = Relocatable::from((0, 40));
+
+        load_cairo_pie(
+            &cairo_pie,
+            &mut vm,
+            program_address,
+            execution_segment_address,
+            ret_fp,
+            ret_pc,
+        )
+        .expect("Failed to load the Cairo PIE");
+
+        for (builtin_ptr, p0) in [(add_mod_ptr, 7u64), (mul_mod_ptr, 11)] {
+            let instance: Vec<_> = (0..7)
+                .map(|offset| vm.get_maybe(&(builtin_ptr + offset).unwrap()).unwrap())
+                .collect();
+            assert_eq!(instance[0], Felt252::from(p0).into());
+            assert_eq!(instance[4], Relocatable::from((1, 14)).into());
+            assert_eq!(instance[5], Relocatable::from((1, 26)).into());
+            assert_eq!(instance[6], Felt252::ONE.into());
+        }
+    }
+
+    #[test]
+    fn test_load_cairo_pie_invalid_mod_builtin_data() {
+        let mut cairo_pie = mod_builtins_pie();
+        cairo_pie
+            .additional_data
+            .0
+            .insert(BuiltinName::mul_mod, BuiltinAdditionalData::Hash(vec![]));
+
+        let result = check_mod_builtin_additional_data(&cairo_pie);
+        assert_matches!(
+            result,
+            Err(ModBuiltinDataError::InvalidCairoPieModBuiltinData(
+                BuiltinName::mul_mod
+            ))
+        );
+    }
 
     #[test]
     fn test_relocate_value() {
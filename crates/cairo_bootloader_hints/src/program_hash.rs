@@ -1,8 +1,11 @@
+use blake2::{Blake2s256, Digest};
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::types::relocatable::MaybeRelocatable;
 use cairo_vm::vm::runners::cairo_pie::StrippedProgram;
 use cairo_vm::Felt252;
-use starknet_crypto::{poseidon_hash_many, FieldElement};
+use starknet_crypto::{pedersen_hash, poseidon_hash_many, FieldElement};
+
+use crate::types::BootloaderVersion;
 
 type HashFunction = fn(&FieldElement, &FieldElement) -> FieldElement;
 
@@ -31,6 +34,29 @@ pub enum ProgramHashError {
     Felt252ToFieldElementConversionFailed,
 }
 
+/// Hash function used to compute a program hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramHashFunction {
+    /// Pedersen hash chain over the data prefixed by its length.
+    Pedersen,
+    /// Poseidon hash of the data.
+    Poseidon,
+    /// Blake2s hash of the data encoded as 32-bit words, truncated to 224 bits. This is the hash
+    /// computed by the bootloader of this repository.
+    Blake2s,
+}
+
+impl ProgramHashFunction {
+    /// Returns the hash function selected by the `use_poseidon` flag of a task.
+    pub fn from_use_poseidon(use_poseidon: bool) -> Self {
+        if use_poseidon {
+            Self::Poseidon
+        } else {
+            Self::Pedersen
+        }
+    }
+}
+
 /// Computes a hash chain over the data, in the following order:
 ///     h(data[0], h(data[1], h(..., h(data[n-2], data[n-1])))).
 ///
@@ -84,23 +110,71 @@ fn maybe_relocatable_to_field_element(
     felt_to_field_element(felt)
 }
 
-/// Computes the Pedersen hash of a program.
+/// Encodes felts into 32-bit words, like `encode_felt252_to_u32s` in Cairo's `blake2s.cairo`.
+///
+/// Felts below 2^63 are encoded as the two words of their 64-bit big-endian value. The others
+/// are encoded as the eight words of their 256-bit big-endian value, with the most significant
+/// bit set (i.e. `+ 2**255`) to tell them apart.
+pub fn encode_felt252_to_u32s(data: &[FieldElement]) -> Vec<u32> {
+    let mut words = Vec::with_capacity(data.len() * 2);
+    for felt in data {
+        let bytes = felt.to_bytes_be();
+        let is_small = bytes[..24].iter().all(|b| *b == 0) && bytes[24] < 0x80;
+        let start = if is_small { 24 } else { 0 };
+        let first_word = words.len();
+        words.extend(
+            bytes[start..]
+                .chunks_exact(4)
+                .map(|chunk| u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+        );
+        if !is_small {
+            words[first_word] |= 1 << 31;
+        }
+    }
+    words
+}
+
+/// Hashes felts like `encode_felt252_data_and_calc_224_bit_blake_hash` in Cairo's
+/// `blake2s.cairo`: Blake2s-256 of the little-endian bytes of the encoded words, of which the
+/// first seven little-endian words form the hash.
+pub fn encode_felt252_data_and_calc_224_bit_blake_hash(data: &[FieldElement]) -> FieldElement {
+    let bytes: Vec<u8> = encode_felt252_to_u32s(data)
+        .into_iter()
+        .flat_map(u32::to_le_bytes)
+        .collect();
+    let digest = Blake2s256::digest(&bytes);
+
+    // hash = words[0] + words[1] * 2**32 + ... + words[6] * 2**192
+    digest[..28]
+        .chunks_exact(4)
+        .rev()
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .fold(FieldElement::ZERO, |hash, word| {
+            hash * FieldElement::from(1u64 << 32) + FieldElement::from(word)
+        })
+}
+
+/// Computes the hash of a program.
 ///
 /// Reimplements this Python function:
 /// ```no-run
-/// def compute_program_hash_chain(program: ProgramBase, bootloader_version=0):
+/// def compute_program_hash_chain(program: ProgramBase, use_poseidon: bool, bootloader_version=0):
 ///     builtin_list = [from_bytes(builtin.encode("ascii")) for builtin in program.builtins]
 ///     # The program header below is missing the data length, which is later added to the data_chain.
 ///     program_header = [bootloader_version, program.main, len(program.builtins)] + builtin_list
 ///     data_chain = program_header + program.data
 ///
-///    if use_poseidon:
+///     if use_poseidon:
 ///         return poseidon_hash_many(data_chain)
 ///     return compute_hash_chain([len(data_chain)] + data_chain)
 /// ```
+///
+/// With `Blake2s`, the data chain is hashed with `encode_felt252_data_and_calc_224_bit_blake_hash`,
+/// as `compute_program_hash` does in `execute_task.cairo`.
 pub fn compute_program_hash_chain(
     program: &StrippedProgram,
-    bootloader_version: usize,
+    bootloader_version: BootloaderVersion,
+    hash_function: ProgramHashFunction,
 ) -> Result<FieldElement, ProgramHashError> {
     let program_main = program.main;
     let program_main = FieldElement::from(program_main);
@@ -126,22 +200,24 @@ pub fn compute_program_hash_chain(
         .collect();
     let program_data = program_data?;
 
-    let data_chain_len = program_header.len() + builtin_list.len() + program_data.len();
-    let data_chain_len_vec = vec![FieldElement::from(data_chain_len)];
-
-    // Prepare a chain of iterators to feed to the hash function
-    let data_chain = [
-        data_chain_len_vec,
-        program_header,
-        builtin_list,
-        program_data,
-    ];
-
-    let payload: Vec<FieldElement> = data_chain
+    let data_chain: Vec<FieldElement> = [program_header, builtin_list, program_data]
         .into_iter()
-        .flat_map(|v| v.into_iter())
-        .collect::<Vec<_>>();
-    let hash = poseidon_hash_many(&payload);
+        .flatten()
+        .collect();
+
+    let hash = match hash_function {
+        ProgramHashFunction::Poseidon => poseidon_hash_many(&data_chain),
+        ProgramHashFunction::Blake2s => {
+            encode_felt252_data_and_calc_224_bit_blake_hash(&data_chain)
+        }
+        ProgramHashFunction::Pedersen => {
+            let data_chain_len = FieldElement::from(data_chain.len());
+            compute_hash_chain(
+                std::iter::once(&data_chain_len).chain(data_chain.iter()),
+                pedersen_hash,
+            )?
+        }
+    };
     Ok(hash)
 }
 
@@ -151,7 +227,6 @@ mod tests {
 
     use cairo_vm::types::program::Program;
     use rstest::rstest;
-    use starknet_crypto::pedersen_hash;
 
     use super::*;
 
@@ -172,22 +247,130 @@ mod tests {
         assert_eq!(computed_hash, expected_hash);
     }
 
+    /// A small program with builtins, laid out by hand to check the composition of the data
+    /// chain independently of the sample programs.
+    fn stripped_program() -> StrippedProgram {
+        StrippedProgram {
+            data: vec![
+                MaybeRelocatable::from(Felt252::from(0x40780017fff7fffu64)),
+                MaybeRelocatable::from(Felt252::from(1)),
+                MaybeRelocatable::from(Felt252::from(0x208b7fff7fff7ffeu64)),
+            ],
+            builtins: vec![BuiltinName::output, BuiltinName::pedersen],
+            main: 1,
+            prime: (),
+        }
+    }
+
+    fn expected_data_chain(bootloader_version: u64) -> Vec<FieldElement> {
+        vec![
+            FieldElement::from(bootloader_version),
+            FieldElement::from(1u64),
+            FieldElement::from(2u64),
+            FieldElement::from_byte_slice_be(b"output").unwrap(),
+            FieldElement::from_byte_slice_be(b"pedersen").unwrap(),
+            FieldElement::from(0x40780017fff7fffu64),
+            FieldElement::from(1u64),
+            FieldElement::from(0x208b7fff7fff7ffeu64),
+        ]
+    }
+
+    #[rstest]
+    #[case::version_0(0)]
+    #[case::version_1(1)]
+    fn test_compute_program_hash_chain_pedersen(#[case] bootloader_version: u64) {
+        let program_hash = compute_program_hash_chain(
+            &stripped_program(),
+            bootloader_version,
+            ProgramHashFunction::Pedersen,
+        )
+        .expect("Failed to compute program hash.");
+
+        let data_chain = expected_data_chain(bootloader_version);
+        let data_chain_len = FieldElement::from(data_chain.len());
+        let expected_hash = data_chain
+            .iter()
+            .rev()
+            .fold(None, |acc, x| match acc {
+                None => Some(*x),
+                Some(acc) => Some(pedersen_hash(x, &acc)),
+            })
+            .map(|hash| pedersen_hash(&data_chain_len, &hash))
+            .unwrap();
+        assert_eq!(program_hash, expected_hash);
+    }
+
+    #[rstest]
+    #[case::small(FieldElement::from(0x1_0000_0002u64), vec![1, 2])]
+    #[case::largest_small(FieldElement::from((1u64 << 63) - 1), vec![0x7fff_ffff, 0xffff_ffff])]
+    #[case::smallest_big(
+        FieldElement::from(1u64 << 63),
+        vec![0x8000_0000, 0, 0, 0, 0, 0, 0x8000_0000, 0]
+    )]
+    fn test_encode_felt252_to_u32s(#[case] felt: FieldElement, #[case] expected_words: Vec<u32>) {
+        assert_eq!(encode_felt252_to_u32s(&[felt]), expected_words);
+    }
+
+    #[rstest]
+    // The first 28 bytes of the Blake2s-256 digest of the empty string, as a little-endian
+    // integer.
+    #[case::empty(vec![], "0xfd0d251b1ea5a12c48b6551f7c4a3542d02111e194809079307a2169")]
+    #[case::small(
+        vec![FieldElement::from(1u64), FieldElement::from(2u64), FieldElement::from(3u64)],
+        "0xf8c16cbd65de33ddbf4a6915144a5c11cc93e4cc1594b14cb159d171"
+    )]
+    #[case::big(
+        vec![FieldElement::from(1u64 << 63)],
+        "0xa3e129384614b3de3a7a6bee8d592ef9cd30c70304e7e84b52702efc"
+    )]
+    fn test_encode_felt252_data_and_calc_224_bit_blake_hash(
+        #[case] data: Vec<FieldElement>,
+        #[case] expected_hash: &str,
+    ) {
+        let hash = encode_felt252_data_and_calc_224_bit_blake_hash(&data);
+        assert_eq!(format!("{hash:#x}"), expected_hash);
+    }
+
+    #[rstest]
+    #[case::version_0(0)]
+    #[case::version_1(1)]
+    fn test_compute_program_hash_chain_blake2s(#[case] bootloader_version: u64) {
+        let program_hash = compute_program_hash_chain(
+            &stripped_program(),
+            bootloader_version,
+            ProgramHashFunction::Blake2s,
+        )
+        .expect("Failed to compute program hash.");
+
+        // Like Poseidon, the data chain is hashed without its length.
+        let expected_hash = encode_felt252_data_and_calc_224_bit_blake_hash(&expected_data_chain(
+            bootloader_version,
+        ));
+        assert_eq!(program_hash, expected_hash);
+    }
+
+    #[test]
+    fn test_program_hash_functions_differ() {
+        let program = stripped_program();
+        let pedersen_hash =
+            compute_program_hash_chain(&program, 0, ProgramHashFunction::Pedersen).unwrap();
+        let poseidon_hash =
+            compute_program_hash_chain(&program, 0, ProgramHashFunction::Poseidon).unwrap();
+        assert_ne!(pedersen_hash, poseidon_hash);
+    }
+
     #[rstest]
-    // TODO: Fix this test, use Poseidon instead of Pedersen
-    #[ignore]
     // Expected hashes generated with `cairo-hash-program`
     #[case::fibonacci(
-        "./dependencies/test-programs/cairo0/fibonacci/fibonacci.json",
+        "../../dependencies/test-programs/cairo0/fibonacci/fibonacci.json",
         "0x6fc56a47599a5cc20bb3c6d4c5397f872bb6269f036e383f4c13986d4020952"
     )]
-    #[ignore]
     #[case::field_arithmetic(
-        "./dependencies/test-programs/cairo0/field-arithmetic/field_arithmetic.json",
+        "../../dependencies/test-programs/cairo0/field-arithmetic/field_arithmetic.json",
         "0xdc5a7432daec36bb707aa9f8cbcd60a2c5a4f5b16dbe7a4b6d96d5bfdd2a43"
     )]
-    #[ignore]
     #[case::keccak_copy_inputs(
-        "./dependencies/test-programs/cairo0/keccak-copy-inputs/keccak_copy_inputs.json",
+        "../../dependencies/test-programs/cairo0/keccak-copy-inputs/keccak_copy_inputs.json",
         "0x79e69539b9bbcc863519fb17f864c3439277cd851146f30d1ce0232fb358632"
     )]
     fn test_compute_program_hash_chain(
@@ -196,15 +379,44 @@ mod tests {
     ) {
         let program =
             Program::from_file(program_path.as_path(), Some("main"))
-                .expect("Could not load program. Did you compile the sample programs? Run `make test` in the root directory.");
+                .expect("Could not load program. Did you compile the sample programs? Run `make test-programs` in the root directory.");
         let stripped_program = program.get_stripped_program().unwrap();
         let bootloader_version = 0;
 
-        let program_hash = compute_program_hash_chain(&stripped_program, bootloader_version)
-            .expect("Failed to compute program hash.");
+        let program_hash = compute_program_hash_chain(
+            &stripped_program,
+            bootloader_version,
+            ProgramHashFunction::Pedersen,
+        )
+        .expect("Failed to compute program hash.");
 
         let program_hash_hex = format!("{:#x}", program_hash);
 
         assert_eq!(program_hash_hex, expected_program_hash);
     }
+
+    #[rstest]
+    // Expected hash of cairo-lang's `compute_program_hash_chain` with `use_poseidon=True`, computed
+    // with starknet-crypto over the same data chain. `make check-program-hashes`, run in CI,
+    // checks it against cairo-lang as part of the program_hash_from_file cases.
+    #[case::fibonacci_with_hint(
+        "../../examples/fibonacci_with_hint.json",
+        "0x7a496beac59cc4d79f9f29a38142539267f8227c57d6ec229bef352294bd8d9"
+    )]
+    fn test_compute_program_hash_chain_poseidon(
+        #[case] program_path: PathBuf,
+        #[case] expected_program_hash: String,
+    ) {
+        let program = Program::from_file(program_path.as_path(), Some("main"))
+            .expect("Could not load program.");
+
+        let program_hash = compute_program_hash_chain(
+            &program.get_stripped_program().unwrap(),
+            0,
+            ProgramHashFunction::Poseidon,
+        )
+        .expect("Failed to compute program hash.");
+
+        assert_eq!(format!("{program_hash:#x}"), expected_program_hash);
+    }
 }
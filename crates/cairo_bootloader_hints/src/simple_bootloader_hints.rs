@@ -1,5 +1,5 @@
 use crate::fact_topologies::FactTopology;
-use crate::types::{RunProgramTask, SimpleBootloaderInput, TaskSpec};
+use crate::types::{CairoPieTask, RunProgramTask, SimpleBootloaderInput, TaskSpec};
 use crate::vars;
 use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData;
 use cairo_vm::hint_processor::builtin_hint_processor::hint_utils::{
@@ -139,7 +139,7 @@ pub fn set_ap_to_zero_or_one(
     let n_tasks = n_tasks_felt
         .to_usize()
         .ok_or(MathError::Felt252ToUsizeConversion(Box::new(n_tasks_felt)))?;
-    let task_id = simple_bootloader_input.tasks.len() - n_tasks;
+    let task_id = current_task_id(simple_bootloader_input, n_tasks)?;
     let task = simple_bootloader_input.tasks[task_id].load_task();
     let use_poseidon = match task {
         Ok(task) => {
@@ -175,7 +175,7 @@ pub fn set_current_task(
         .to_usize()
         .ok_or(MathError::Felt252ToUsizeConversion(Box::new(n_tasks_felt)))?;
 
-    let task_id = simple_bootloader_input.tasks.len() - n_tasks;
+    let task_id = current_task_id(simple_bootloader_input, n_tasks)?;
     // TODO: it's still unclear how we need to model TaskSpec/Task objects.
     //       Check if we need to keep TaskSpec, or if it needs to be implemented as a trait, etc.
     let task = simple_bootloader_input.tasks[task_id].load_task();
@@ -184,18 +184,40 @@ pub fn set_current_task(
             if let Some(run_program_task) = task.as_any().downcast_ref::<RunProgramTask>() {
                 exec_scopes
                     .insert_value(vars::TASK, TaskSpec::RunProgram(run_program_task.clone()));
+            } else if let Some(cairo_pie_task) = task.as_any().downcast_ref::<CairoPieTask>() {
+                exec_scopes
+                    .insert_value(vars::TASK, TaskSpec::CairoPieTask(cairo_pie_task.clone()));
             }
-            // else if let Some(cairo_pie_task) = task.as_any().downcast_ref::<CairoPieTask>() {
-            //     exec_scopes
-            //         .insert_value(vars::TASK, TaskSpec::CairoPieTask(cairo_pie_task.clone()));
-            // }
         }
-        Err(_) => return Err(HintError::CustomHint("Task not found".into())),
+        Err(e) => {
+            return Err(HintError::CustomHint(
+                format!("Task {task_id}: failed to load the task: {e}").into_boxed_str(),
+            ))
+        }
     }
+    // Not in the Python hint: errors raised while executing the task report its index.
+    exec_scopes.insert_value(vars::TASK_ID, task_id);
 
     Ok(())
 }
 
+/// Returns `len(simple_bootloader_input.tasks) - n_tasks`, the index of the current task.
+fn current_task_id(
+    simple_bootloader_input: &SimpleBootloaderInput,
+    n_tasks: usize,
+) -> Result<usize, HintError> {
+    let total_tasks = simple_bootloader_input.tasks.len();
+    total_tasks
+        .checked_sub(n_tasks)
+        .filter(|task_id| *task_id < total_tasks)
+        .ok_or_else(|| {
+            HintError::CustomHint(
+                format!("Invalid number of remaining tasks {n_tasks}, out of {total_tasks}")
+                    .into_boxed_str(),
+            )
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use std::any::Any;
@@ -243,11 +265,15 @@ mod tests {
                 TaskSpec::RunProgram(RunProgramTask {
                     program: fibonacci.clone(),
                     program_input: HashMap::new(),
+                    user_args: vec![],
+                    hints: HashMap::new(),
                     use_poseidon: true,
                 }),
                 TaskSpec::RunProgram(RunProgramTask {
                     program: fibonacci.clone(),
                     program_input: HashMap::new(),
+                    user_args: vec![],
+                    hints: HashMap::new(),
                     use_poseidon: true,
                 }),
             ],
@@ -391,5 +417,7 @@ mod tests {
         let _task: &Box<dyn Any> = exec_scopes
             .get_any_boxed_ref(vars::TASK)
             .expect("task variable is not set.");
+        let task_id: usize = exec_scopes.get(vars::TASK_ID).unwrap();
+        assert_eq!(task_id, 1);
     }
 }
@@ -2,6 +2,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use cairo_lang_casm::hints::Hint;
 use cairo_vm::serde::deserialize_program::Identifier;
 use cairo_vm::types::errors::program_errors::ProgramError;
 use cairo_vm::types::program::Program;
@@ -9,9 +10,11 @@ use cairo_vm::vm::runners::cairo_pie::CairoPie;
 use cairo_vm::Felt252;
 use serde::Deserialize;
 
+use crate::fact_topologies::{compute_fact_topologies, FactTopology, FactTopologyError};
+
 pub type BootloaderVersion = u64;
 
-pub(crate) type ProgramIdentifiers = HashMap<String, Identifier>;
+pub type ProgramIdentifiers = HashMap<String, Identifier>;
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct BootloaderConfig {
@@ -23,12 +26,35 @@ pub struct BootloaderConfig {
 pub struct CompositePackedOutput {
     pub outputs: Vec<Felt252>,
     pub subtasks: Vec<PackedOutput>,
+    /// Fact topologies of the subtasks, one per subtask.
+    pub fact_topologies: Vec<FactTopology>,
 }
 
 impl CompositePackedOutput {
     pub fn elements_for_hash(&self) -> &Vec<Felt252> {
         &self.outputs
     }
+
+    /// Returns the fact topologies of the plain subtasks, recursively unpacking the composite
+    /// subtasks.
+    ///
+    /// Reimplements the following Python code:
+    /// ```no-run
+    /// def get_plain_fact_topologies(self) -> List[FactTopology]:
+    ///     subtasks_fact_topologies = []
+    ///     assert len(self.subtasks) == len(self.fact_topologies)
+    ///     for subtask, fact_topology in zip(self.subtasks, self.fact_topologies):
+    ///         if isinstance(subtask, PlainPackedOutput):
+    ///             subtasks_fact_topologies.append(fact_topology)
+    ///         elif isinstance(subtask, CompositePackedOutput):
+    ///             subtasks_fact_topologies.extend(subtask.get_plain_fact_topologies())
+    ///         else:
+    ///             raise NotImplementedError(...)
+    ///     return subtasks_fact_topologies
+    /// ```
+    pub fn get_plain_fact_topologies(&self) -> Result<Vec<&FactTopology>, FactTopologyError> {
+        compute_fact_topologies(&self.subtasks, &self.fact_topologies)
+    }
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -65,10 +91,34 @@ impl TaskSpec {
     }
 }
 
+/// An argument of a Cairo 1 task, written to memory by its `WriteRunParam` hints. Mirrors
+/// `cairo_lang_runner::Arg`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserArg {
+    Value(Felt252),
+    /// Written as a `(start, end)` pointer pair to a new segment holding the elements.
+    Array(Vec<UserArg>),
+}
+
+impl UserArg {
+    /// The size of the argument in memory.
+    pub fn size(&self) -> usize {
+        match self {
+            UserArg::Value(_) => 1,
+            UserArg::Array(_) => 2,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct RunProgramTask {
     pub program: Program,
     pub program_input: HashMap<String, serde_json::Value>,
+    /// Run params of a Cairo 1 task, indexed by the `WriteRunParam` index.
+    pub user_args: Vec<Vec<UserArg>>,
+    /// Hints of a Cairo 1 task, keyed by their string representation in the program. The
+    /// other hints of the program are Cairo 0 hints.
+    pub hints: HashMap<String, Hint>,
     pub use_poseidon: bool,
 }
 
@@ -91,9 +141,21 @@ impl RunProgramTask {
         Self {
             program,
             program_input,
+            user_args: vec![],
+            hints: HashMap::new(),
             use_poseidon,
         }
     }
+
+    pub fn with_hints(mut self, hints: HashMap<String, Hint>) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    pub fn with_user_args(mut self, user_args: Vec<Vec<UserArg>>) -> Self {
+        self.user_args = user_args;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
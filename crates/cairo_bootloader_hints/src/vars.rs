@@ -1,7 +1,7 @@
 /// Deserialized bootloader input.
 pub const BOOTLOADER_INPUT: &str = "bootloader_input";
 
-/// The bootloader program, as a Program object.
+/// Identifiers of the bootloader program, to resolve its labels from the hints.
 pub const BOOTLOADER_PROGRAM_IDENTIFIERS: &str = "bootloader_program_identifiers";
 
 /// Saved state of the output builtin.
@@ -28,6 +28,12 @@ pub const TASKS: &str = "tasks";
 /// Current simple bootloader task.
 pub const TASK: &str = "task";
 
+/// Index of the current simple bootloader task.
+pub const TASK_ID: &str = "task_id";
+
+/// Run params of the current Cairo 1 task, read by its `WriteRunParam` hints.
+pub const USER_ARGS: &str = "user_args";
+
 /// Program data segment. Used in `execute_task()`.
 pub const PROGRAM_DATA_BASE: &str = "program_data_base";
 
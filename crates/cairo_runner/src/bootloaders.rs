@@ -1,11 +1,124 @@
+use std::fmt;
+
 use cairo_vm::types::errors::program_errors::ProgramError;
 use cairo_vm::types::program::Program;
 
 pub use cairo_bootloader_hints::*;
 
-const BOOTLOADER: &[u8] = include_bytes!("../../../resources/bootloader.json");
+/// The bootloader programs a build can be compiled from.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootloaderKind {
+    /// `bootloader/bootloader.cairo` of this repository, the full bootloader hashing the task
+    /// programs with Blake2s, for the Stwo prover.
+    Stwo,
+}
+
+impl fmt::Display for BootloaderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BootloaderKind::Stwo => "stwo",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A compiled bootloader embedded in the binary.
+#[derive(Debug)]
+pub struct BootloaderBuild {
+    pub kind: BootloaderKind,
+    /// Version of cairo-lang the bootloader was compiled with, which selects the variants of
+    /// some hints (see `codes.rs`).
+    pub cairo_lang_version: &'static str,
+    program: &'static [u8],
+}
+
+/// The embedded bootloaders. `make compile` regenerates the Stwo one; other builds are added
+/// here along with their compiled program in `resources/`.
+pub const BOOTLOADER_BUILDS: &[BootloaderBuild] = &[BootloaderBuild {
+    kind: BootloaderKind::Stwo,
+    cairo_lang_version: "0.13.5",
+    program: include_bytes!("../../../resources/stwo-bootloader.json"),
+}];
+
+/// The bootloader run when none is selected.
+pub const DEFAULT_BOOTLOADER: &BootloaderBuild = &BOOTLOADER_BUILDS[0];
+
+#[derive(thiserror::Error, Debug)]
+pub enum FindBootloaderError {
+    #[error("No {requested} bootloader is embedded, available builds: {available}")]
+    Unknown {
+        requested: String,
+        available: String,
+    },
+    #[error(
+        "The {} bootloader is registered as compiled with cairo-lang {}, but its program records {}",
+        .build.kind,
+        .build.cairo_lang_version,
+        .detected.as_deref().unwrap_or("no version")
+    )]
+    VersionMismatch {
+        build: &'static BootloaderBuild,
+        detected: Option<String>,
+    },
+}
+
+impl BootloaderBuild {
+    /// Loads the bootloader as a Cairo VM `Program` object.
+    pub fn load(&self) -> Result<Program, ProgramError> {
+        Program::from_bytes(self.program, Some("main"))
+    }
+
+    /// Reads the version of cairo-lang the bootloader was compiled with from the program.
+    pub fn detect_cairo_lang_version(&self) -> Option<String> {
+        detect_cairo_lang_version(self.program)
+    }
+}
+
+impl fmt::Display for BootloaderBuild {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (cairo-lang {})", self.kind, self.cairo_lang_version)
+    }
+}
+
+/// Returns the embedded bootloader of the given kind, compiled with `cairo_lang_version` if
+/// set, or else with the latest version. Fails if the compiled program does not record the
+/// version of cairo-lang the build declares, as the hints would not match the program.
+pub fn find_bootloader(
+    kind: BootloaderKind,
+    cairo_lang_version: Option<&str>,
+) -> Result<&'static BootloaderBuild, FindBootloaderError> {
+    let build = BOOTLOADER_BUILDS
+        .iter()
+        .filter(|build| build.kind == kind)
+        .filter(|build| cairo_lang_version.map_or(true, |v| build.cairo_lang_version == v))
+        .last()
+        .ok_or_else(|| FindBootloaderError::Unknown {
+            requested: match cairo_lang_version {
+                Some(version) => format!("{kind} (cairo-lang {version})"),
+                None => kind.to_string(),
+            },
+            available: BOOTLOADER_BUILDS
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        })?;
+
+    let detected = build.detect_cairo_lang_version();
+    if detected.as_deref() != Some(build.cairo_lang_version) {
+        return Err(FindBootloaderError::VersionMismatch { build, detected });
+    }
+    Ok(build)
+}
+
+/// Reads the version of cairo-lang a program was compiled with, from the `compiler_version`
+/// field of the compiled JSON.
+pub fn detect_cairo_lang_version(program: &[u8]) -> Option<String> {
+    let program: serde_json::Value = serde_json::from_slice(program).ok()?;
+    program["compiler_version"].as_str().map(str::to_string)
+}
 
-/// Loads the bootloader and returns it as a Cairo VM `Program` object.
+/// Loads the default bootloader as a Cairo VM `Program` object.
 pub fn load_bootloader() -> Result<Program, ProgramError> {
-    Program::from_bytes(BOOTLOADER, Some("main"))
+    DEFAULT_BOOTLOADER.load()
 }
@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use cairo_lang_casm::hints::Hint;
+use cairo_vm::types::errors::program_errors::ProgramError;
+use cairo_vm::types::program::Program;
+use serde::Serialize;
+
+use crate::hint_processor::BootloaderHintProcessor;
+use crate::task::{load_executable, BootloaderTaskError, EntrypointSelector, ProgramKind};
+
+#[derive(thiserror::Error, Debug)]
+pub enum HintCoverageError {
+    #[error(transparent)]
+    Task(#[from] BootloaderTaskError),
+
+    #[error("Failed to read program: {0}")]
+    Program(#[from] ProgramError),
+
+    #[error("{0} is a Cairo PIE, which the bootloader runs without its hints")]
+    CairoPie(PathBuf),
+}
+
+/// The hint processor implementing a hint, in the order `BootloaderHintProcessor` dispatches
+/// hints.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HintKind {
+    /// A hint of the bootloader, a print hint or a hint registered with `add_hint`.
+    Bootloader,
+    /// A bootloader hint that adds hints to the program, such as the one calling a task.
+    Extensive,
+    /// A hint of the builtin hint processor of the Cairo VM, mostly the Cairo 0 common library.
+    CairoVm,
+    /// A core hint of a Cairo 1 program, run by the Cairo 1 hint processor of the Cairo VM.
+    Cairo1Core,
+    /// An external hint of a Cairo 1 executable, such as `WriteRunParam`.
+    Cairo1External,
+    /// A hint that no hint processor implements.
+    Unknown,
+}
+
+impl HintKind {
+    /// Returns which hint processor implements a Cairo 1 hint.
+    pub fn of_cairo1_hint(hint: &Hint) -> Self {
+        match hint {
+            Hint::Core(_) => HintKind::Cairo1Core,
+            Hint::External(_) => HintKind::Cairo1External,
+            // Tasks have no syscall handler.
+            Hint::Starknet(_) => HintKind::Unknown,
+        }
+    }
+
+    pub fn is_supported(self) -> bool {
+        self != HintKind::Unknown
+    }
+}
+
+impl fmt::Display for HintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HintKind::Bootloader => "bootloader",
+            HintKind::Extensive => "extensive",
+            HintKind::CairoVm => "cairo-vm builtin",
+            HintKind::Cairo1Core => "cairo 1 core",
+            HintKind::Cairo1External => "cairo 1 external",
+            HintKind::Unknown => "unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A hint code of a program, with the number of pcs it runs at.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct HintUsage {
+    pub code: String,
+    pub kind: HintKind,
+    pub count: usize,
+}
+
+/// The hints of a program, sorted by kind and code.
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HintCoverage {
+    pub hints: Vec<HintUsage>,
+}
+
+impl HintCoverage {
+    /// Returns the hints that no hint processor implements.
+    pub fn unsupported(&self) -> impl Iterator<Item = &HintUsage> {
+        self.hints.iter().filter(|hint| !hint.kind.is_supported())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.unsupported().next().is_none()
+    }
+
+    /// Returns the number of distinct hint codes of each kind.
+    pub fn count_by_kind(&self) -> BTreeMap<HintKind, usize> {
+        let mut counts = BTreeMap::new();
+        for hint in &self.hints {
+            *counts.entry(hint.kind).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// Classifies the hints of `program`. `cairo1_hints` are the hints of a Cairo 1 program, keyed
+/// by their code in the program; the other codes are Cairo 0 hints.
+pub fn hint_coverage(
+    hint_processor: &BootloaderHintProcessor,
+    program: &Program,
+    cairo1_hints: &HashMap<String, Hint>,
+) -> HintCoverage {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for hint in program.iter_hints() {
+        *counts.entry(hint.code.as_str()).or_default() += 1;
+    }
+
+    let mut hints: Vec<HintUsage> = counts
+        .into_iter()
+        .map(|(code, count)| {
+            let kind = match cairo1_hints.get(code) {
+                Some(hint) => HintKind::of_cairo1_hint(hint),
+                None => hint_processor.classify_hint(code),
+            };
+            HintUsage {
+                code: code.to_string(),
+                kind,
+                count,
+            }
+        })
+        .collect();
+    hints.sort_by(|a, b| (a.kind, &a.code).cmp(&(b.kind, &b.code)));
+
+    HintCoverage { hints }
+}
+
+/// Classifies the hints of the program in the file at `path`. The kind of the file is detected
+/// from its content if not given.
+pub fn hint_coverage_from_file(
+    hint_processor: &BootloaderHintProcessor,
+    path: &Path,
+    kind: Option<ProgramKind>,
+) -> Result<HintCoverage, HintCoverageError> {
+    let kind = match kind {
+        Some(kind) => kind,
+        None => ProgramKind::detect(path)?,
+    };
+    let (program, cairo1_hints) = match kind {
+        ProgramKind::Cairo0 => (Program::from_file(path, Some("main"))?, HashMap::new()),
+        ProgramKind::Executable => load_executable(path, EntrypointSelector::default())?,
+        ProgramKind::CairoPie => return Err(HintCoverageError::CairoPie(path.to_path_buf())),
+    };
+    Ok(hint_coverage(hint_processor, &program, &cairo1_hints))
+}
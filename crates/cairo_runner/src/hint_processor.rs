@@ -1,43 +1,175 @@
-use std::{any::Any, collections::HashMap, rc::Rc};
+use std::{any::Any, collections::HashMap, fmt, rc::Rc};
 
-use cairo_bootloader_hints::{ExtensiveHintImpl, HintImpl};
-use cairo_lang_casm::{hints::{ExternalHint, Hint}, operand::ResOperand};
-use cairo_lang_runner::{casm_run::{cell_ref_to_relocatable, extract_relocatable, get_val}, Arg};
+use cairo_bootloader_hints::{
+    codes::EXECUTE_TASK_CALL_TASK, debug::TracingSink, vars, DebugHint, DebugMessage, DebugSink,
+    ExtensiveHintImpl, HintImpl, LogLevel, UserArg,
+};
+use cairo_lang_casm::{
+    hints::{ExternalHint, Hint},
+    operand::ResOperand,
+};
+use cairo_lang_runner::casm_run::{cell_ref_to_relocatable, extract_relocatable, get_val};
 use cairo_vm::{
-    any_box, hint_processor::{
+    hint_processor::{
         builtin_hint_processor::builtin_hint_processor_definition::{
             BuiltinHintProcessor, HintProcessorData,
         },
         cairo_1_hint_processor::hint_processor::Cairo1HintProcessor,
         hint_processor_definition::{HintExtension, HintProcessorLogic, HintReference},
-    }, serde::deserialize_program::ApTracking, types::{exec_scope::ExecutionScopes, relocatable::Relocatable}, vm::{
-        errors::{hint_errors::HintError, vm_errors::VirtualMachineError}, runners::cairo_runner::ResourceTracker,
+    },
+    serde::deserialize_program::ApTracking,
+    types::{exec_scope::ExecutionScopes, program::Program, relocatable::Relocatable},
+    vm::{
+        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+        runners::cairo_runner::ResourceTracker,
         vm_core::VirtualMachine,
-    }, Felt252
+    },
+    Felt252,
 };
 
 use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::builtin_hint_codes::is_builtin_hint;
+use crate::hint_coverage::{hint_coverage, HintKind};
+
+/// A `WriteRunParam` hint of a Cairo 1 task asked for a run param that the task does not have.
+#[derive(thiserror::Error, Debug)]
+#[error("Task {task_id}: run param {index} requested at pc {pc}, but the task has {n_params} run params\nHint: {hint}")]
+pub struct RunParamError {
+    pub task_id: usize,
+    pub pc: Relocatable,
+    pub index: Box<Felt252>,
+    pub n_params: usize,
+    pub hint: String,
+}
+
+impl From<RunParamError> for HintError {
+    fn from(value: RunParamError) -> Self {
+        HintError::CustomHint(value.to_string().into_boxed_str())
+    }
+}
+
+/// A frame of the traceback of a Cairo 1 panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracebackFrame {
+    /// Pc of the call instruction, or of the panic for the innermost frame.
+    pub pc: Relocatable,
+    /// `pc` relative to the start of the task program, `None` for frames of the bootloader.
+    pub task_pc: Option<usize>,
+}
+
+/// The call stack of a Cairo 1 task when it panicked, outermost call first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicTraceback {
+    pub task_id: Option<usize>,
+    pub frames: Vec<TracebackFrame>,
+}
+
+impl fmt::Display for PanicTraceback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.task_id {
+            Some(task_id) => writeln!(f, "Task {task_id} panicked")?,
+            None => writeln!(f, "A task panicked")?,
+        }
+        writeln!(f, "Traceback (most recent call last):")?;
+        for frame in &self.frames {
+            match frame.task_pc {
+                Some(pc) => writeln!(f, "    <task> (pc {pc})")?,
+                None => writeln!(f, "    <bootloader> (pc {})", frame.pc)?,
+            }
+        }
+        Ok(())
+    }
+}
+
 pub struct BootloaderHintProcessor {
     builtin_hint_proc: BuiltinHintProcessor,
     cairo1_builtin_hint_proc: Cairo1HintProcessor,
     hints: HashMap<String, HintImpl>,
     extensive_hints: HashMap<String, ExtensiveHintImpl>,
+    debug_hints: HashMap<String, DebugHint>,
+    debug_sink: Box<dyn DebugSink>,
+    log_level: LogLevel,
     external_hint_proc: ExternalHintProcessor,
-    pub string_to_hint: HashMap<String, Hint>,
 }
 
 impl BootloaderHintProcessor {
-    pub fn new(string_to_hint: HashMap<String, Hint>, user_args: Vec<Vec<Arg>>) -> Self {
+    pub fn new() -> Self {
         Self {
             builtin_hint_proc: BuiltinHintProcessor::new_empty(),
-            cairo1_builtin_hint_proc: Cairo1HintProcessor::new(Default::default(), Default::default(), false),
+            cairo1_builtin_hint_proc: Cairo1HintProcessor::new(
+                Default::default(),
+                Default::default(),
+                false,
+            ),
             hints: Self::hints(),
             extensive_hints: Self::extensive_hints(),
-            string_to_hint,
-            external_hint_proc: ExternalHintProcessor::new(user_args),
+            debug_hints: cairo_bootloader_hints::get_debug_hints(),
+            debug_sink: Box::new(TracingSink),
+            log_level: LogLevel::default(),
+            external_hint_proc: ExternalHintProcessor::new(),
         }
     }
 
+    /// Registers a Rust implementation of a Cairo 0 hint, keyed by its code. Overrides the
+    /// bootloader hint with the same code, if any.
+    pub fn add_hint(&mut self, hint_code: String, hint_impl: HintImpl) {
+        self.hints.insert(hint_code, hint_impl);
+    }
+
+    /// Sends the messages of the print hints of Cairo programs to `debug_sink` instead of
+    /// `tracing`.
+    pub fn set_debug_sink(&mut self, debug_sink: Box<dyn DebugSink>) {
+        self.debug_sink = debug_sink;
+    }
+
+    /// Sets the level below which print hints are skipped, `Info` by default.
+    pub fn set_log_level(&mut self, log_level: LogLevel) {
+        self.log_level = log_level;
+    }
+
+    /// Returns the traceback of the last Cairo 1 panic of the current task, if any.
+    pub fn take_panic_traceback(&mut self) -> Option<PanicTraceback> {
+        self.external_hint_proc.panic_traceback.take()
+    }
+
+    /// Returns the messages kept by the debug sink so far.
+    pub fn take_debug_messages(&mut self) -> Vec<DebugMessage> {
+        self.debug_sink.take_messages()
+    }
+
+    /// Returns which hint processor implements a Cairo 0 hint.
+    pub fn classify_hint(&self, hint_code: &str) -> HintKind {
+        if self.hints.contains_key(hint_code) || self.debug_hints.contains_key(hint_code) {
+            HintKind::Bootloader
+        } else if self.extensive_hints.contains_key(hint_code) {
+            HintKind::Extensive
+        } else if is_builtin_hint(hint_code) {
+            HintKind::CairoVm
+        } else {
+            HintKind::Unknown
+        }
+    }
+
+    /// Returns whether a Cairo 0 hint is implemented, by the bootloader hints, the hints added
+    /// with `add_hint` or the builtin hint processor of the Cairo VM.
+    pub fn supports_hint(&self, hint_code: &str) -> bool {
+        self.classify_hint(hint_code).is_supported()
+    }
+
+    /// Returns the codes of the hints of `program` that are not implemented, sorted and without
+    /// duplicates. `cairo1_hints` are the hints of a Cairo 1 program, see `hint_coverage`.
+    pub fn unsupported_hints(
+        &self,
+        program: &Program,
+        cairo1_hints: &HashMap<String, Hint>,
+    ) -> Vec<String> {
+        hint_coverage(self, program, cairo1_hints)
+            .unsupported()
+            .map(|hint| hint.code.clone())
+            .collect()
+    }
+
     fn hints() -> HashMap<String, HintImpl> {
         let mut hints = HashMap::new();
         hints.extend(cairo_bootloader_hints::get_hints());
@@ -51,6 +183,12 @@ impl BootloaderHintProcessor {
     }
 }
 
+impl Default for BootloaderHintProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HintProcessorLogic for BootloaderHintProcessor {
     fn execute_hint(
         &mut self,
@@ -69,37 +207,73 @@ impl HintProcessorLogic for BootloaderHintProcessor {
         hint_data: &Box<dyn Any>,
         constants: &HashMap<String, Felt252>,
     ) -> Result<HintExtension, HintError> {
-        // // // If this is a Cairo 1 hint (cairo_lang_casm::hints::Hint), execute it directly
+        // The index of the current task is in scope from the time the simple bootloader selects
+        // it until the next one.
+        let task_span = match exec_scopes.get::<usize>(vars::TASK_ID) {
+            Ok(task_id) => tracing::debug_span!("task", task_id),
+            Err(_) => tracing::Span::none(),
+        };
+        let _task = task_span.enter();
+        let _hint = tracing::trace_span!("hint", pc = %vm.get_pc()).entered();
+
+        self.dispatch_hint(vm, exec_scopes, hint_data, constants)
+    }
+}
+
+impl BootloaderHintProcessor {
+    fn dispatch_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &Box<dyn Any>,
+        constants: &HashMap<String, Felt252>,
+    ) -> Result<HintExtension, HintError> {
+        // If this is a Cairo 1 hint (cairo_lang_casm::hints::Hint), execute it directly
         if let Some(hint) = hint_data.downcast_ref::<Hint>() {
-            match hint {
+            tracing::trace!(?hint, "Executing Cairo 1 hint");
+            return match hint {
                 Hint::External(external_hint) => {
-                    println!("Cairo 1 External Hint: {:?}", hint);
-                    let r = self.external_hint_proc.execute_external_hint(vm, external_hint)?;
-                    // println!("Cairo 1 External Hint Result: {:?}", r);
-                    return Ok(HintExtension::default());
-                }
-                _ => {
-                    println!("Cairo 1 Builtin Hint: {:?}", hint);
-                    let r = self.cairo1_builtin_hint_proc
-                        .execute(vm, exec_scopes, hint).map(|_| HintExtension::default());
-                    // println!("Cairo 1 Builtin Hint Result: {:?}", r);
-                    return r;
+                    self.external_hint_proc.execute_external_hint(
+                        vm,
+                        exec_scopes,
+                        external_hint,
+                    )?;
+                    Ok(HintExtension::default())
                 }
-            }
+                _ => self
+                    .cairo1_builtin_hint_proc
+                    .execute(vm, exec_scopes, hint)
+                    .map(|_| HintExtension::default()),
+            };
         }
 
         if let Some(hpd) = hint_data.downcast_ref::<HintProcessorData>() {
             let hint_code: &str = hpd.code.as_str();
+            tracing::trace!(hint_code, "Executing Cairo 0 hint");
             if let Some(hint_impl) = self.hints.get(hint_code) {
-                println!("Custom Hint: {:?}", hint_code);
                 return hint_impl(vm, exec_scopes, hpd, &HashMap::new())
                     .map(|_| HintExtension::default());
             }
 
+            if let Some(debug_hint) = self.debug_hints.get(hint_code) {
+                if debug_hint.level <= self.log_level {
+                    let message = (debug_hint.format)(vm, hpd)?;
+                    self.debug_sink.log(DebugMessage {
+                        task_id: exec_scopes.get(vars::TASK_ID).ok(),
+                        level: debug_hint.level,
+                        message,
+                    });
+                }
+                return Ok(HintExtension::default());
+            }
+
             if let Some(hint_impl) = self.extensive_hints.get(hint_code) {
+                if hint_code == EXECUTE_TASK_CALL_TASK {
+                    // A traceback only describes the task that is running.
+                    self.external_hint_proc.panic_traceback = None;
+                }
                 let r = hint_impl(
                     &mut self.builtin_hint_proc,
-                    &self.string_to_hint,
                     vm,
                     exec_scopes,
                     hpd,
@@ -120,25 +294,23 @@ impl HintProcessorLogic for BootloaderHintProcessor {
 
 impl ResourceTracker for BootloaderHintProcessor {}
 
-
+#[derive(Default)]
 pub struct ExternalHintProcessor {
-    pub user_args: Vec<Vec<Arg>>,
     markers: Vec<Vec<Felt252>>,
-    panic_traceback: Vec<(Relocatable, Relocatable)>,
+    panic_traceback: Option<PanicTraceback>,
 }
 
 impl ExternalHintProcessor {
-    pub fn new(user_args: Vec<Vec<Arg>>) -> Self {
-        Self {
-            user_args,
-            markers: Vec::new(),
-            panic_traceback: Vec::new(),
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 
+    /// Executes a Cairo 1 external hint. `WriteRunParam` hints read the run params of the
+    /// currently executing task, from the task scope.
     fn execute_external_hint(
         &mut self,
         vm: &mut VirtualMachine,
+        exec_scopes: &ExecutionScopes,
         core_hint: &ExternalHint,
     ) -> Result<(), HintError> {
         match core_hint {
@@ -152,16 +324,28 @@ impl ExternalHintProcessor {
                 },
             )?,
             ExternalHint::WriteRunParam { index, dst } => {
-                let index = get_val(vm, index)?.to_usize().expect("Got a bad index.");
-                let mut stack = vec![(cell_ref_to_relocatable(dst, vm), &self.user_args[index])];
+                let task_id: usize = exec_scopes.get(vars::TASK_ID)?;
+                let index_felt = get_val(vm, index)?;
+                let user_args: &Vec<Vec<UserArg>> = exec_scopes.get_ref(vars::USER_ARGS)?;
+                let task_args = index_felt
+                    .to_usize()
+                    .and_then(|index| user_args.get(index))
+                    .ok_or_else(|| RunParamError {
+                        task_id,
+                        pc: vm.get_pc(),
+                        index: Box::new(index_felt),
+                        n_params: user_args.len(),
+                        hint: format!("{core_hint:?}"),
+                    })?;
+                let mut stack = vec![(cell_ref_to_relocatable(dst, vm), task_args)];
                 while let Some((mut buffer, values)) = stack.pop() {
                     for value in values {
                         match value {
-                            Arg::Value(v) => {
+                            UserArg::Value(v) => {
                                 vm.insert_value(buffer, v)?;
                                 buffer += 1;
                             }
-                            Arg::Array(arr) => {
+                            UserArg::Array(arr) => {
                                 let arr_buffer = vm.add_memory_segment();
                                 stack.push((arr_buffer, arr));
                                 vm.insert_value(buffer, arr_buffer)?;
@@ -181,7 +365,7 @@ impl ExternalHintProcessor {
                 // Setting the panic backtrace if the given flag is panic.
                 if flag == 0x70616e6963u64.into() {
                     let mut fp = vm.get_fp();
-                    self.panic_traceback = vec![(vm.get_pc(), fp)];
+                    let mut pcs = vec![vm.get_pc()];
                     // Fetch the fp and pc traceback entries
                     loop {
                         let ptr_at_offset = |offset: usize| {
@@ -191,12 +375,10 @@ impl ExternalHintProcessor {
                         let Some(ret_pc) = ptr_at_offset(1) else {
                             break;
                         };
-                        println!("ret_pc: {ret_pc}");
                         // Get fp traceback.
                         let Some(ret_fp) = ptr_at_offset(2) else {
                             break;
                         };
-                        println!("ret_fp: {ret_fp}");
                         if ret_fp == fp {
                             break;
                         }
@@ -204,20 +386,42 @@ impl ExternalHintProcessor {
 
                         let call_instruction = |offset: usize| -> Option<Relocatable> {
                             let ptr = (ret_pc - offset).ok()?;
-                            println!("ptr: {ptr}");
                             let inst = vm.get_integer(ptr).ok()?;
-                            println!("inst: {inst}");
                             let inst_short = inst.to_u64()?;
                             (inst_short & 0x7000_0000_0000_0000 == 0x1000_0000_0000_0000)
                                 .then_some(ptr)
                         };
                         if let Some(call_pc) = call_instruction(1).or_else(|| call_instruction(2)) {
-                            self.panic_traceback.push((call_pc, fp));
+                            pcs.push(call_pc);
                         } else {
                             break;
                         }
                     }
-                    self.panic_traceback.reverse();
+                    pcs.reverse();
+
+                    // The task program is loaded at `program_address`, pcs of the task are
+                    // relative to it.
+                    let program_address: Option<Relocatable> =
+                        exec_scopes.get(vars::PROGRAM_ADDRESS).ok();
+                    let task_pc = |pc: Relocatable| {
+                        let program_address = program_address?;
+                        if pc.segment_index != program_address.segment_index {
+                            return None;
+                        }
+                        pc.offset.checked_sub(program_address.offset)
+                    };
+                    let traceback = PanicTraceback {
+                        task_id: exec_scopes.get(vars::TASK_ID).ok(),
+                        frames: pcs
+                            .into_iter()
+                            .map(|pc| TracebackFrame {
+                                pc,
+                                task_pc: task_pc(pc),
+                            })
+                            .collect(),
+                    };
+                    tracing::debug!(?traceback, "Cairo 1 panic");
+                    self.panic_traceback = Some(traceback);
                 }
             }
         }
@@ -244,7 +448,7 @@ impl ExternalHintProcessor {
     }
 
     /// The size in memory of the arguments.
-    fn args_size(args: &[Arg]) -> usize {
-        args.iter().map(Arg::size).sum()
+    fn args_size(args: &[UserArg]) -> usize {
+        args.iter().map(UserArg::size).sum()
     }
-}
\ No newline at end of file
+}
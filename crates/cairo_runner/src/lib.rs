@@ -1,8 +1,18 @@
-use cairo_bootloader_hints::{types::BootloaderInput, vars::BOOTLOADER_INPUT};
-use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_bootloader_hints::{
+    types::{BootloaderInput, ProgramIdentifiers},
+    vars::{BOOTLOADER_INPUT, BOOTLOADER_PROGRAM_IDENTIFIERS},
+};
+use cairo_vm::types::{exec_scope::ExecutionScopes, program::Program};
 
 pub mod bootloaders;
+pub mod builtin_hint_codes;
+pub mod hint_coverage;
 pub mod hint_processor;
+pub mod manifest;
+pub mod program_hash;
+pub mod prover_input;
+pub mod recursion;
+pub mod runner;
 pub mod task;
 
 /// Inserts the bootloader input in the execution scopes.
@@ -12,3 +22,16 @@ pub fn insert_bootloader_input(
 ) {
     exec_scopes.insert_value(BOOTLOADER_INPUT, bootloader_input);
 }
+
+/// Inserts the identifiers of the bootloader program in the execution scopes, so that the hints
+/// can resolve the labels of the bootloader.
+pub fn insert_bootloader_program_identifiers(
+    exec_scopes: &mut ExecutionScopes,
+    bootloader_program: &Program,
+) {
+    let identifiers: ProgramIdentifiers = bootloader_program
+        .iter_identifiers()
+        .map(|(name, identifier)| (name.to_string(), identifier.clone()))
+        .collect();
+    exec_scopes.insert_value(BOOTLOADER_PROGRAM_IDENTIFIERS, identifiers);
+}
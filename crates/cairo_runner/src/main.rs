@@ -1,213 +1,374 @@
-use bincode::enc::write::Writer;
-use cairo_lang_casm::hints::Hint;
-use cairo_lang_runner::Arg;
-use cairo_runner::bootloaders::load_bootloader;
+use cairo_bootloader_hints::fact_topologies::read_fact_topologies_file;
+use cairo_bootloader_hints::facts::{compute_bootloader_facts, FactHashFunction};
+use cairo_bootloader_hints::LogLevel;
+use cairo_lang_executable::executable::EntryPointKind;
+use cairo_runner::bootloaders::{find_bootloader, load_bootloader, BootloaderKind};
+use cairo_runner::hint_coverage::{hint_coverage, hint_coverage_from_file, HintUsage};
 use cairo_runner::hint_processor::BootloaderHintProcessor;
-use cairo_runner::insert_bootloader_input;
-use cairo_lang_execute_utils::user_args_from_flags;
-
-use cairo_runner::task::make_bootloader_tasks;
-use clap::Parser;
+use cairo_runner::manifest::Manifest;
+use cairo_runner::program_hash::{
+    program_hash, program_hash_from_file, ProgramHashFunction, ProgramKind,
+};
+use cairo_runner::runner::{parse_layout, BootloaderRunner, RunOptions};
+use cairo_runner::task::{list_entrypoints, TaskSource};
+use cairo_vm::Felt252;
+use clap::{Parser, Subcommand, ValueEnum};
 use std::collections::HashMap;
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
+use tracing_subscriber::EnvFilter;
 
-use cairo_vm::air_public_input::PublicInput;
-use cairo_vm::cairo_run::{
-    cairo_run_program_with_initial_scope, write_encoded_memory, write_encoded_trace, CairoRunConfig,
-};
-use cairo_vm::types::exec_scope::ExecutionScopes;
-use cairo_vm::types::layout_name::LayoutName;
-use cairo_vm::types::program::Program;
-use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
-use cairo_vm::vm::runners::cairo_runner::CairoRunner;
-use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
-use cairo_vm::Felt252;
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log more: `-v` for debug logs, such as the tasks being called, `-vv` for trace logs,
+    /// such as every hint executed. `RUST_LOG` takes precedence when set.
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only log errors.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Format of the logs, which are written to stderr.
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    log_format: LogFormat,
+}
 
-use cairo_bootloader_hints::types::BootloaderConfig;
-use cairo_bootloader_hints::types::BootloaderInput;
-use cairo_bootloader_hints::types::PackedOutput;
-use cairo_bootloader_hints::types::SimpleBootloaderInput;
-use cairo_bootloader_hints::types::TaskSpec;
-// use cairo_bootloader_hints::types::BootloaderHintProcessor;
-
-use serde::{Deserialize, Serialize};
-
-fn cairo_run_bootloader_in_proof_mode(
-    bootloader_program: &Program,
-    tasks: Vec<TaskSpec>,
-    string_to_hint: HashMap<String, Hint>,
-    user_args: Vec<Vec<Arg>>,
-) -> Result<CairoRunner, CairoRunError> {
-    let mut hint_processor = BootloaderHintProcessor::new(string_to_hint, user_args);
-
-    let cairo_run_config = CairoRunConfig {
-        entrypoint: "main",
-        trace_enabled: true,
-        relocate_mem: true,
-        layout: LayoutName::all_cairo_stwo,
-        proof_mode: true,
-        secure_run: None,
-        disable_trace_padding: true,
-        allow_missing_builtins: None,
-        dynamic_layout_params: None,
-        ..Default::default()
-    };
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum LogFormat {
+    Text,
+    Json,
+}
 
-    // Build the bootloader input
-    let n_tasks = tasks.len();
-    let bootloader_input = BootloaderInput {
-        simple_bootloader_input: SimpleBootloaderInput {
-            fact_topologies_path: None,
-            single_page: false,
-            tasks,
-        },
-        bootloader_config: BootloaderConfig {
-            simple_bootloader_program_hash: Felt252::from(0),
-            supported_cairo_verifier_program_hashes: vec![],
-        },
-        packed_outputs: vec![PackedOutput::Plain(vec![]); n_tasks],
-        ignore_fact_topologies: true,
+/// Installs the global `tracing` subscriber.
+fn init_logging(cli: &Cli) {
+    let level = match (cli.quiet, cli.verbose) {
+        (true, _) => "error",
+        (false, 0) => "info",
+        (false, 1) => "debug",
+        (false, _) => "trace",
     };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    match cli.log_format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
+
+/// Level of the print hints of Cairo programs, see `LogLevel`.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum CairoLogLevel {
+    Off,
+    Info,
+    Debug,
+}
+
+impl From<CairoLogLevel> for LogLevel {
+    fn from(value: CairoLogLevel) -> Self {
+        match value {
+            CairoLogLevel::Off => LogLevel::Off,
+            CairoLogLevel::Info => LogLevel::Info,
+            CairoLogLevel::Debug => LogLevel::Debug,
+        }
+    }
+}
 
-    let mut exec_scopes = ExecutionScopes::new();
-    insert_bootloader_input(&mut exec_scopes, bootloader_input);
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run tasks in the bootloader and write the prover input.
+    Run(RunArgs),
+    /// Print the hash of a program, like `cairo-hash-program`.
+    HashProgram(HashProgramArgs),
+    /// Print the facts of the tasks of a bootloader run.
+    Facts(FactsArgs),
+    /// List the entrypoints of a Cairo 1 executable.
+    Entrypoints(EntrypointsArgs),
+    /// Check that the hints of a program, or of an embedded bootloader, are implemented.
+    Hints(HintsArgs),
+}
 
-    // Run the bootloader
-    cairo_run_program_with_initial_scope(
-        bootloader_program,
-        &cairo_run_config,
-        &mut hint_processor,
-        exec_scopes,
-    )
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    /// A task to run, as `[<kind>:]<path>[@<entrypoint>][,<input_file>]`. Can be repeated;
+    /// tasks run in the given order. The kind (`cairo0`, `executable` or `cairo_pie`) is
+    /// detected from the file content if omitted. The entrypoint of a Cairo 1 executable is
+    /// `bootloader` (the default) or an index, see the `entrypoints` command. The input file is
+    /// the JSON program input of a Cairo 0 program, or the args file of a Cairo 1 executable.
+    #[arg(short, long = "task", required_unless_present = "manifest")]
+    tasks: Vec<TaskSource>,
+    /// A JSON or TOML manifest describing the whole bootloader input.
+    #[arg(short, long, conflicts_with = "tasks")]
+    manifest: Option<PathBuf>,
+    /// Directory to write the prover input to. Nothing is written if omitted.
+    #[arg(short, long)]
+    output_path: Option<PathBuf>,
+    /// Embedded bootloader to run the tasks in.
+    #[arg(long, value_enum, default_value_t = BootloaderKind::Stwo)]
+    bootloader: BootloaderKind,
+    /// Version of cairo-lang the bootloader was compiled with. The latest embedded build of
+    /// the bootloader is used if omitted.
+    #[arg(long)]
+    cairo_lang_version: Option<String>,
+    /// Layout to run the bootloader with, e.g. `recursive` or `starknet_with_keccak`.
+    #[arg(long, default_value = "all_cairo_stwo")]
+    layout: String,
+    /// Params of the `dynamic` layout, as a JSON file.
+    #[arg(long, required_if_eq("layout", "dynamic"))]
+    cairo_layout_params_file: Option<PathBuf>,
+    /// Run in execution mode instead of proof mode. No prover input can be produced.
+    #[arg(long, conflicts_with = "output_path")]
+    no_proof_mode: bool,
+    /// Pad the trace to the next power of two.
+    #[arg(long)]
+    trace_padding: bool,
+    /// Run the Cairo VM security checks at the end of the run.
+    #[arg(long)]
+    secure_run: bool,
+    /// File to write the bootloader output to, as a JSON array of felts.
+    #[arg(long)]
+    program_output: Option<PathBuf>,
+    /// Level of the `info_*` and `debug_*` hints of the programs, logged under the `cairo`
+    /// target. `debug` also needs `-v` to be shown.
+    #[arg(long, value_enum, default_value_t = CairoLogLevel::Info)]
+    cairo_log_level: CairoLogLevel,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PrivateInput {
-    pub trace_path: PathBuf,
-    pub memory_path: PathBuf,
+#[derive(clap::Args, Debug)]
+struct HashProgramArgs {
+    /// Path of the program.
+    program: PathBuf,
+    /// Kind of the program file. Detected from its content if omitted.
+    #[arg(short, long)]
+    kind: Option<ProgramKind>,
+    /// Use Poseidon instead of the Pedersen hash chain.
+    #[arg(long)]
+    use_poseidon: bool,
+    /// Use Blake2s, which gives the program hash written to the output of this bootloader.
+    #[arg(long, conflicts_with = "use_poseidon")]
+    blake2s: bool,
 }
 
-pub struct FileWriter {
-    buf_writer: std::io::BufWriter<std::fs::File>,
-    bytes_written: usize,
+#[derive(clap::Args, Debug)]
+struct FactsArgs {
+    /// Bootloader output, as written by `run --program-output`.
+    program_output: PathBuf,
+    /// Fact topologies of the tasks, as written to the `fact_topologies_path` of the manifest.
+    /// Every task output is assumed to be a single page if omitted.
+    #[arg(short, long)]
+    fact_topologies: Option<PathBuf>,
+    /// Use Poseidon instead of Keccak.
+    #[arg(long)]
+    use_poseidon: bool,
+    /// Hash of the bootloader program that produced the output, for the fact of the whole
+    /// output. Defaults to the Pedersen hash of the default embedded bootloader.
+    #[arg(long, value_parser = parse_felt)]
+    bootloader_program_hash: Option<Felt252>,
 }
 
-impl Writer for FileWriter {
-    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
-        self.buf_writer
-            .write_all(bytes)
-            .map_err(|e| bincode::error::EncodeError::Io {
-                inner: e,
-                index: self.bytes_written,
-            })?;
+fn parse_felt(value: &str) -> Result<Felt252, String> {
+    Felt252::from_hex(value).map_err(|_| format!("Invalid hex field element: {value}"))
+}
 
-        self.bytes_written += bytes.len();
+#[derive(clap::Args, Debug)]
+struct EntrypointsArgs {
+    /// Path of the Cairo 1 executable.
+    executable: PathBuf,
+}
 
-        Ok(())
+#[derive(clap::Args, Debug)]
+struct HintsArgs {
+    /// Path of the compiled Cairo 0 program or Cairo 1 executable. The embedded bootloader
+    /// selected with `--bootloader` is checked if omitted.
+    program: Option<PathBuf>,
+    /// Kind of the program file. Detected from its content if omitted.
+    #[arg(short, long, requires = "program")]
+    kind: Option<ProgramKind>,
+    /// Embedded bootloader to check.
+    #[arg(long, value_enum, default_value_t = BootloaderKind::Stwo, conflicts_with = "program")]
+    bootloader: BootloaderKind,
+    /// Version of cairo-lang the bootloader was compiled with, the latest if omitted.
+    #[arg(long, conflicts_with = "program")]
+    cairo_lang_version: Option<String>,
+    /// List all the hints, not only the unsupported ones.
+    #[arg(long)]
+    all: bool,
+    /// Print the classified hints as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    init_logging(&cli);
+
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::HashProgram(args) => hash_program(args),
+        Command::Facts(args) => facts(args),
+        Command::Entrypoints(args) => entrypoints(args),
+        Command::Hints(args) => hints(args),
     }
 }
 
-impl FileWriter {
-    fn new(buf_writer: std::io::BufWriter<std::fs::File>) -> Self {
-        Self {
-            buf_writer,
-            bytes_written: 0,
+fn hints(args: HintsArgs) -> Result<(), Box<dyn Error>> {
+    let hint_processor = BootloaderHintProcessor::new();
+    let coverage = match &args.program {
+        Some(path) => hint_coverage_from_file(&hint_processor, path, args.kind)?,
+        None => {
+            let build = find_bootloader(args.bootloader, args.cairo_lang_version.as_deref())?;
+            hint_coverage(&hint_processor, &build.load()?, &HashMap::new())
+        }
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&coverage)?);
+    } else {
+        for (kind, count) in coverage.count_by_kind() {
+            println!("{kind}: {count}");
+        }
+        let listed: Vec<&HintUsage> = if args.all {
+            coverage.hints.iter().collect()
+        } else {
+            coverage.unsupported().collect()
+        };
+        for hint in listed {
+            println!("\n[{}, {} pcs]\n{}", hint.kind, hint.count, hint.code);
         }
     }
-}
 
-pub fn prover_input_from_runner<'r>(
-    runner: &'r CairoRunner,
-    output_dir: &Path,
-) -> (PrivateInput, PublicInput<'r>) {
-    let public_input = runner.get_air_public_input().unwrap();
-    let trace = runner
-        .relocated_trace
-        .as_ref()
-        .unwrap()
-        .iter()
-        .map(|x| RelocatedTraceEntry {
-            ap: x.ap,
-            fp: x.fp,
-            pc: x.pc,
-        })
-        .collect::<Vec<_>>();
+    let n_unsupported = coverage.unsupported().count();
+    if n_unsupported > 0 {
+        return Err(format!("{n_unsupported} hints are not implemented").into());
+    }
 
-    let trace_path = output_dir.join("trace");
-    let trace_file = File::create(&trace_path).unwrap();
-    let mut trace_writer = FileWriter::new(std::io::BufWriter::with_capacity(
-        3 * 1024 * 1024,
-        trace_file,
-    ));
-    write_encoded_trace(&trace, &mut trace_writer).unwrap();
-
-    let memory_path = output_dir.join("memory");
-    let memory_file = File::create(&memory_path).unwrap();
-    let mut memory_writer = FileWriter::new(std::io::BufWriter::with_capacity(
-        5 * 1024 * 1024,
-        memory_file,
-    ));
-    write_encoded_memory(&runner.relocated_memory, &mut memory_writer).unwrap();
-
-    let private_input = PrivateInput {
-        trace_path: std::fs::canonicalize(&trace_path).unwrap(),
-        memory_path: std::fs::canonicalize(&memory_path).unwrap(),
-    };
-    (private_input, public_input)
+    Ok(())
 }
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(short, long)]
-    input_path: PathBuf,
-    #[arg(short, long)]
-    program_path: PathBuf,
-    #[arg(short, long)]
-    output_path: PathBuf,
+fn entrypoints(args: EntrypointsArgs) -> Result<(), Box<dyn Error>> {
+    for (index, entrypoint) in list_entrypoints(&args.executable)?.iter().enumerate() {
+        let kind = match entrypoint.kind {
+            EntryPointKind::Bootloader => "bootloader",
+            EntryPointKind::Standalone => "standalone (cannot be bootloaded)",
+        };
+        let builtins: Vec<&str> = entrypoint
+            .builtins
+            .iter()
+            .map(|builtin| builtin.to_str())
+            .collect();
+        println!(
+            "{index}: {kind}, offset {}, builtins: [{}]",
+            entrypoint.offset,
+            builtins.join(", ")
+        );
+    }
+
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args = Args::parse();
-    let bootloader_program = load_bootloader()?;
-    let program_input = serde_json::from_reader(std::fs::File::open(args.input_path.clone()).unwrap())?;
-    let program_path = args.program_path;
+fn facts(args: FactsArgs) -> Result<(), Box<dyn Error>> {
+    let program_output: Vec<Felt252> =
+        serde_json::from_reader(std::fs::File::open(&args.program_output)?)?;
+    let fact_topologies = args
+        .fact_topologies
+        .as_deref()
+        .map(read_fact_topologies_file)
+        .transpose()?;
+    let hash_function = if args.use_poseidon {
+        FactHashFunction::Poseidon
+    } else {
+        FactHashFunction::Keccak
+    };
+
+    let bootloader_program_hash = match args.bootloader_program_hash {
+        Some(program_hash) => program_hash,
+        None => program_hash(
+            &load_bootloader()?.get_stripped_program()?,
+            ProgramHashFunction::Pedersen,
+        )?,
+    };
 
-    let (task, string_to_hint) = make_bootloader_tasks(&program_path, Some(program_input))?;
+    let facts = compute_bootloader_facts(
+        &bootloader_program_hash,
+        &program_output,
+        fact_topologies.as_deref(),
+        hash_function,
+    )?;
+    println!("{}", serde_json::to_string_pretty(&facts)?);
 
-    let user_args = user_args_from_flags(Some(&args.input_path), &[])?;
+    Ok(())
+}
 
-    // println!("string_to_hint: {:#?}", string_to_hint);
-    let mut runner = cairo_run_bootloader_in_proof_mode(&bootloader_program, vec![task], string_to_hint, vec![vec![Arg::Array(user_args)]])?;
+fn hash_program(args: HashProgramArgs) -> Result<(), Box<dyn Error>> {
+    let hash_function = if args.blake2s {
+        ProgramHashFunction::Blake2s
+    } else {
+        ProgramHashFunction::from_use_poseidon(args.use_poseidon)
+    };
+    let program_hash = program_hash_from_file(&args.program, args.kind, hash_function)?;
+    println!("{program_hash:#x}");
 
-    let mut output_buffer = "Program Output:\n".to_string();
-    runner.vm.write_output(&mut output_buffer)?;
-    print!("{output_buffer}");
-    println!("--------------------------------");
+    Ok(())
+}
 
-    std::fs::create_dir_all(&args.output_path).unwrap();
-    let (private_input, public_input) = prover_input_from_runner(&runner, &args.output_path);
+fn run(args: RunArgs) -> Result<(), Box<dyn Error>> {
+    let manifest = match &args.manifest {
+        Some(path) => Manifest::from_file(path)?,
+        None => Manifest::from_tasks(args.tasks),
+    };
+    let mut run_options = RunOptions {
+        layout: parse_layout(&args.layout)?,
+        proof_mode: !args.no_proof_mode,
+        disable_trace_padding: !args.trace_padding,
+        secure_run: Some(args.secure_run),
+        ..Default::default()
+    };
+    if let Some(path) = &args.cairo_layout_params_file {
+        run_options = run_options.with_dynamic_layout_params_file(path)?;
+    }
+
+    let bootloader_build = find_bootloader(args.bootloader, args.cairo_lang_version.as_deref())?;
 
-    let priv_json = serde_json::to_string(&private_input).unwrap();
-    let pub_json = serde_json::to_string(&public_input).unwrap();
-    std::fs::write(args.output_path.join("priv.json"), priv_json).unwrap();
-    std::fs::write(args.output_path.join("pub.json"), pub_json).unwrap();
+    let result = BootloaderRunner::from_manifest(&manifest)?
+        .with_bootloader_build(bootloader_build)?
+        .with_run_options(run_options)
+        .with_log_level(args.cairo_log_level.into())
+        .run()?;
+
+    if let Some(output_path) = &args.output_path {
+        result.write_prover_input(output_path)?;
+    }
+    if let Some(program_output_path) = &args.program_output {
+        std::fs::write(program_output_path, serde_json::to_string(&result.output)?)?;
+    }
 
-    let resources = runner
-        .get_execution_resources()
-        .expect("failed to get execution resources, but the run was successful");
+    // The artifacts are written, failing to decode the output only affects what is printed.
+    println!("Program Output:");
+    let decoded_output = result
+        .bootloader_output()
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|bootloader_output| Ok(serde_json::to_string_pretty(&bootloader_output)?));
+    match decoded_output {
+        Ok(decoded_output) => println!("{decoded_output}"),
+        Err(error) => tracing::warn!(%error, "Failed to decode the bootloader output"),
+    }
+    println!("--------------------------------");
 
-    let builtin_instance_counter = resources
+    let builtin_instance_counter = result
+        .execution_resources
         .builtin_instance_counter
-        .into_iter()
-        .map(|(k, v)| (k.to_string(), v))
+        .iter()
+        .map(|(k, v)| (k.to_string(), *v))
         .collect::<Vec<_>>();
 
-    println!("n_steps: {}", resources.n_steps);
-    println!("n_memory_holes: {}", resources.n_memory_holes);
+    println!("n_steps: {}", result.execution_resources.n_steps);
+    println!(
+        "n_memory_holes: {}",
+        result.execution_resources.n_memory_holes
+    );
     println!("builtin_instance_counter: {:#?}", builtin_instance_counter);
 
     Ok(())
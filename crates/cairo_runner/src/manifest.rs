@@ -0,0 +1,345 @@
+//! Declarative description of a bootloader run.
+//!
+//! A manifest lists the tasks to run and the rest of the `BootloaderInput`. It can be written
+//! in JSON or TOML, the format being picked from the file extension:
+//!
+//! ```toml
+//! version = 1
+//! ignore_fact_topologies = false
+//!
+//! [bootloader_config]
+//! simple_bootloader_program_hash = "0x0"
+//! supported_cairo_verifier_program_hashes = []
+//!
+//! [[tasks]]
+//! type = "executable"
+//! path = "fibonacci.executable.json"
+//! args_file = "fibonacci.args.json"
+//!
+//! [[tasks]]
+//! type = "cairo0"
+//! path = "fibonacci_compiled.json"
+//! program_input = "fibonacci_input.json"
+//!
+//! [[tasks]]
+//! type = "cairo_pie"
+//! path = "noop.zip"
+//! use_poseidon = true
+//! ```
+//!
+//! Relative paths are resolved against the directory of the manifest.
+//!
+//! A task can verify the proof of a previous bootloader run, whose tasks are then unpacked into
+//! the output of this run (see `crate::recursion`):
+//!
+//! ```toml
+//! [bootloader_config]
+//! simple_bootloader_program_hash = "0x..."
+//! supported_cairo_verifier_program_hashes = ["0x..."]
+//!
+//! [[verified_runs]]
+//! task = 0
+//! program_output = "inner/output.json"
+//! fact_topologies = "inner/fact_topologies.json"
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use cairo_bootloader_hints::types::{
+    BootloaderConfig, BootloaderInput, PackedOutput, SimpleBootloaderInput, TaskSpec,
+};
+use cairo_vm::Felt252;
+use serde::Deserialize;
+
+use crate::recursion::{RecursionError, VerifiedRun};
+use crate::task::TaskSource;
+
+/// Version of the manifest schema understood by this crate.
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ManifestError {
+    #[error("Failed to read manifest {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Invalid JSON manifest: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid TOML manifest: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Unsupported manifest version {found}, expected {expected}")]
+    UnsupportedVersion { found: u32, expected: u32 },
+
+    #[error("Manifest does not contain any task")]
+    NoTasks,
+
+    #[error("Manifest has {n_packed_outputs} packed outputs but {n_tasks} tasks")]
+    PackedOutputsLength {
+        n_packed_outputs: usize,
+        n_tasks: usize,
+    },
+
+    #[error("Task {task_index}: file not found: {path}")]
+    FileNotFound { task_index: usize, path: PathBuf },
+
+    #[error("Verified runs cannot be combined with explicit packed outputs")]
+    ConflictingPackedOutputs,
+
+    #[error("Verified run of task {task_index}: the manifest has {n_tasks} tasks")]
+    VerifiedRunTaskOutOfBounds { task_index: usize, n_tasks: usize },
+
+    #[error("Task {0} verifies more than one run")]
+    DuplicateVerifiedRun(usize),
+
+    #[error(
+        "Verified runs require supported Cairo verifier program hashes in the bootloader config"
+    )]
+    NoSupportedVerifiers,
+
+    #[error("Verified run of task {task_index}: {error}")]
+    VerifiedRun {
+        task_index: usize,
+        error: RecursionError,
+    },
+}
+
+/// Only the version field, to reject manifests of other versions before parsing their content.
+#[derive(Deserialize)]
+struct ManifestVersion {
+    version: u32,
+}
+
+/// A versioned description of a full `BootloaderInput`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub version: u32,
+    pub tasks: Vec<TaskSource>,
+    #[serde(default = "default_bootloader_config")]
+    pub bootloader_config: BootloaderConfig,
+    /// One packed output per task. Defaults to an empty plain output for every task.
+    #[serde(default)]
+    pub packed_outputs: Option<Vec<PackedOutput>>,
+    #[serde(default)]
+    pub fact_topologies_path: Option<PathBuf>,
+    #[serde(default)]
+    pub single_page: bool,
+    /// Write all the task outputs to page 0. See `BootloaderInput::ignore_fact_topologies`.
+    ///
+    /// Unlike `BootloaderInput`, this defaults to true, as `run --task` always wrote the task
+    /// outputs to page 0 before manifests were introduced. Set it to false to split the outputs
+    /// into pages following the fact topologies of the tasks, as the Cairo 0 bootloader does.
+    #[serde(default = "default_ignore_fact_topologies")]
+    pub ignore_fact_topologies: bool,
+    /// Previous runs verified by some of the tasks. Their packed outputs are built from the
+    /// runs, the other tasks get an empty plain output.
+    #[serde(default)]
+    pub verified_runs: Vec<VerifiedRun>,
+}
+
+fn default_bootloader_config() -> BootloaderConfig {
+    BootloaderConfig {
+        simple_bootloader_program_hash: Felt252::ZERO,
+        supported_cairo_verifier_program_hashes: vec![],
+    }
+}
+
+fn default_ignore_fact_topologies() -> bool {
+    true
+}
+
+impl Manifest {
+    /// Builds a manifest with the default configuration for the given tasks.
+    pub fn from_tasks(tasks: Vec<TaskSource>) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            tasks,
+            bootloader_config: default_bootloader_config(),
+            packed_outputs: None,
+            fact_topologies_path: None,
+            single_page: false,
+            ignore_fact_topologies: default_ignore_fact_topologies(),
+            verified_runs: vec![],
+        }
+    }
+
+    /// Loads and validates a manifest. Files with a `.toml` extension are parsed as TOML,
+    /// anything else as JSON.
+    pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| ManifestError::Io(path.to_path_buf(), e))?;
+        let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+
+        let version = if is_toml {
+            toml::from_str::<ManifestVersion>(&content)?.version
+        } else {
+            serde_json::from_str::<ManifestVersion>(&content)?.version
+        };
+        if version != MANIFEST_VERSION {
+            return Err(ManifestError::UnsupportedVersion {
+                found: version,
+                expected: MANIFEST_VERSION,
+            });
+        }
+
+        let mut manifest: Manifest = if is_toml {
+            toml::from_str(&content)?
+        } else {
+            serde_json::from_str(&content)?
+        };
+
+        if let Some(base_dir) = path.parent() {
+            manifest.resolve_paths(base_dir);
+        }
+        manifest.validate()?;
+
+        Ok(manifest)
+    }
+
+    /// Makes all the relative paths of the manifest relative to `base_dir`.
+    fn resolve_paths(&mut self, base_dir: &Path) {
+        let resolve = |path: &mut PathBuf| {
+            if path.is_relative() {
+                *path = base_dir.join(&*path);
+            }
+        };
+
+        for task in &mut self.tasks {
+            match task {
+                TaskSource::Executable {
+                    path, args_file, ..
+                } => {
+                    resolve(path);
+                    if let Some(args_file) = args_file {
+                        resolve(args_file);
+                    }
+                }
+                TaskSource::Cairo0 {
+                    path,
+                    program_input,
+                    ..
+                } => {
+                    resolve(path);
+                    if let Some(program_input) = program_input {
+                        resolve(program_input);
+                    }
+                }
+                TaskSource::CairoPie { path, .. } => resolve(path),
+                TaskSource::Auto {
+                    path, input_file, ..
+                } => {
+                    resolve(path);
+                    if let Some(input_file) = input_file {
+                        resolve(input_file);
+                    }
+                }
+            }
+        }
+        if let Some(fact_topologies_path) = &mut self.fact_topologies_path {
+            resolve(fact_topologies_path);
+        }
+        for verified_run in &mut self.verified_runs {
+            verified_run.resolve_paths(base_dir);
+        }
+    }
+
+    /// Checks that the manifest describes a run the bootloader can execute.
+    ///
+    /// The version is checked when reading the manifest, see `Manifest::from_file`.
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        if self.tasks.is_empty() {
+            return Err(ManifestError::NoTasks);
+        }
+
+        if let Some(packed_outputs) = &self.packed_outputs {
+            if packed_outputs.len() != self.tasks.len() {
+                return Err(ManifestError::PackedOutputsLength {
+                    n_packed_outputs: packed_outputs.len(),
+                    n_tasks: self.tasks.len(),
+                });
+            }
+        }
+
+        if !self.verified_runs.is_empty() {
+            if self.packed_outputs.is_some() {
+                return Err(ManifestError::ConflictingPackedOutputs);
+            }
+            if self
+                .bootloader_config
+                .supported_cairo_verifier_program_hashes
+                .is_empty()
+            {
+                return Err(ManifestError::NoSupportedVerifiers);
+            }
+        }
+        let mut verifier_tasks = vec![false; self.tasks.len()];
+        for verified_run in &self.verified_runs {
+            let is_verifier = verifier_tasks.get_mut(verified_run.task).ok_or(
+                ManifestError::VerifiedRunTaskOutOfBounds {
+                    task_index: verified_run.task,
+                    n_tasks: self.tasks.len(),
+                },
+            )?;
+            if *is_verifier {
+                return Err(ManifestError::DuplicateVerifiedRun(verified_run.task));
+            }
+            *is_verifier = true;
+        }
+
+        for (task_index, task) in self.tasks.iter().enumerate() {
+            for path in std::iter::once(task.path()).chain(task.input_path()) {
+                if !path.is_file() {
+                    return Err(ManifestError::FileNotFound {
+                        task_index,
+                        path: path.to_path_buf(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the bootloader input from the manifest and the tasks built from `self.tasks`.
+    ///
+    /// Reads the files of the verified runs, if any.
+    pub fn bootloader_input(&self, tasks: Vec<TaskSpec>) -> Result<BootloaderInput, ManifestError> {
+        let packed_outputs = match &self.packed_outputs {
+            Some(packed_outputs) => packed_outputs.clone(),
+            None => self.packed_outputs_from_verified_runs(tasks.len())?,
+        };
+
+        Ok(BootloaderInput {
+            simple_bootloader_input: SimpleBootloaderInput {
+                fact_topologies_path: self.fact_topologies_path.clone(),
+                single_page: self.single_page,
+                tasks,
+            },
+            bootloader_config: self.bootloader_config.clone(),
+            packed_outputs,
+            ignore_fact_topologies: self.ignore_fact_topologies,
+        })
+    }
+
+    fn packed_outputs_from_verified_runs(
+        &self,
+        n_tasks: usize,
+    ) -> Result<Vec<PackedOutput>, ManifestError> {
+        let mut packed_outputs = vec![PackedOutput::Plain(vec![]); n_tasks];
+        for verified_run in &self.verified_runs {
+            let task_index = verified_run.task;
+            let packed_output = packed_outputs.get_mut(task_index).ok_or(
+                ManifestError::VerifiedRunTaskOutOfBounds {
+                    task_index,
+                    n_tasks,
+                },
+            )?;
+            let composite_packed_output = verified_run
+                .load()
+                .map_err(|error| ManifestError::VerifiedRun { task_index, error })?;
+            *packed_output = PackedOutput::Composite(composite_packed_output);
+        }
+        Ok(packed_outputs)
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use cairo_bootloader_hints::execute_task_hints::BOOTLOADER_VERSION;
+pub use cairo_bootloader_hints::program_hash::ProgramHashFunction;
+use cairo_bootloader_hints::program_hash::{compute_program_hash_chain, ProgramHashError};
+use cairo_vm::types::errors::program_errors::ProgramError;
+use cairo_vm::types::program::Program;
+use cairo_vm::vm::runners::cairo_pie::{CairoPie, StrippedProgram};
+use cairo_vm::Felt252;
+
+pub use crate::task::ProgramKind;
+use crate::task::{load_executable, BootloaderTaskError, EntrypointSelector};
+
+#[derive(thiserror::Error, Debug)]
+pub enum HashProgramError {
+    #[error(transparent)]
+    Task(#[from] BootloaderTaskError),
+
+    #[error("Failed to read program: {0}")]
+    Program(#[from] ProgramError),
+
+    #[error("Failed to read PIE: {0}")]
+    Pie(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Hash(#[from] ProgramHashError),
+}
+
+/// Reads the program that the bootloader would load for the file at `path`.
+pub fn load_stripped_program(
+    path: &Path,
+    kind: ProgramKind,
+) -> Result<StrippedProgram, HashProgramError> {
+    let program = match kind {
+        ProgramKind::Cairo0 => Program::from_file(path, Some("main"))?,
+        ProgramKind::Executable => load_executable(path, EntrypointSelector::default())?.0,
+        ProgramKind::CairoPie => return Ok(CairoPie::read_zip_file(path)?.metadata.program),
+    };
+    Ok(program.get_stripped_program()?)
+}
+
+/// Computes the hash of a program, like `cairo-hash-program`.
+pub fn program_hash(
+    program: &StrippedProgram,
+    hash_function: ProgramHashFunction,
+) -> Result<Felt252, HashProgramError> {
+    let hash = compute_program_hash_chain(program, BOOTLOADER_VERSION, hash_function)?;
+    Ok(Felt252::from_bytes_be(&hash.to_bytes_be()))
+}
+
+/// Computes the hash of the program in the file at `path`. The kind of the file is detected
+/// from its content if not given.
+pub fn program_hash_from_file(
+    path: &Path,
+    kind: Option<ProgramKind>,
+    hash_function: ProgramHashFunction,
+) -> Result<Felt252, HashProgramError> {
+    let kind = match kind {
+        Some(kind) => kind,
+        None => ProgramKind::detect(path)?,
+    };
+    let program = load_stripped_program(path, kind)?;
+    program_hash(&program, hash_function)
+}
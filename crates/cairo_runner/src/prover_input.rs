@@ -0,0 +1,138 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use bincode::enc::write::Writer;
+use cairo_vm::air_public_input::{PublicInput, PublicInputError};
+use cairo_vm::cairo_run::{write_encoded_memory, write_encoded_trace, EncodeTraceError};
+use cairo_vm::vm::runners::cairo_runner::CairoRunner;
+use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
+use serde::{Deserialize, Serialize};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ProverInputError {
+    #[error("Failed to compute the AIR public input: {0}")]
+    PublicInput(#[from] PublicInputError),
+
+    #[error("The trace was not relocated, was the bootloader run with tracing enabled?")]
+    TraceNotRelocated,
+
+    #[error("Failed to encode trace or memory: {0}")]
+    Encode(#[from] EncodeTraceError),
+
+    #[error("Failed to write {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Failed to serialize prover input: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Private input of the prover: paths to the binary encoded trace and memory files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateInput {
+    pub trace_path: PathBuf,
+    pub memory_path: PathBuf,
+}
+
+/// A bincode `Writer` writing to a buffered file.
+pub struct FileWriter {
+    buf_writer: BufWriter<File>,
+    bytes_written: usize,
+}
+
+impl Writer for FileWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        self.buf_writer
+            .write_all(bytes)
+            .map_err(|e| bincode::error::EncodeError::Io {
+                inner: e,
+                index: self.bytes_written,
+            })?;
+
+        self.bytes_written += bytes.len();
+
+        Ok(())
+    }
+}
+
+impl FileWriter {
+    pub fn new(buf_writer: BufWriter<File>) -> Self {
+        Self {
+            buf_writer,
+            bytes_written: 0,
+        }
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.buf_writer.flush()
+    }
+}
+
+fn create_file_writer(path: &Path, capacity: usize) -> Result<FileWriter, ProverInputError> {
+    let file = File::create(path).map_err(|e| ProverInputError::Io(path.to_path_buf(), e))?;
+    Ok(FileWriter::new(BufWriter::with_capacity(capacity, file)))
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf, ProverInputError> {
+    std::fs::canonicalize(path).map_err(|e| ProverInputError::Io(path.to_path_buf(), e))
+}
+
+/// Writes the encoded trace and memory of a finished run to `output_dir`, and returns the
+/// private and public inputs of the prover.
+pub fn prover_input_from_runner<'r>(
+    runner: &'r CairoRunner,
+    output_dir: &Path,
+) -> Result<(PrivateInput, PublicInput<'r>), ProverInputError> {
+    let public_input = runner.get_air_public_input()?;
+    let trace = runner
+        .relocated_trace
+        .as_ref()
+        .ok_or(ProverInputError::TraceNotRelocated)?
+        .iter()
+        .map(|x| RelocatedTraceEntry {
+            ap: x.ap,
+            fp: x.fp,
+            pc: x.pc,
+        })
+        .collect::<Vec<_>>();
+
+    let trace_path = output_dir.join("trace");
+    let mut trace_writer = create_file_writer(&trace_path, 3 * 1024 * 1024)?;
+    write_encoded_trace(&trace, &mut trace_writer)?;
+    trace_writer
+        .flush()
+        .map_err(|e| ProverInputError::Io(trace_path.clone(), e))?;
+
+    let memory_path = output_dir.join("memory");
+    let mut memory_writer = create_file_writer(&memory_path, 5 * 1024 * 1024)?;
+    write_encoded_memory(&runner.relocated_memory, &mut memory_writer)?;
+    memory_writer
+        .flush()
+        .map_err(|e| ProverInputError::Io(memory_path.clone(), e))?;
+
+    let private_input = PrivateInput {
+        trace_path: canonicalize(&trace_path)?,
+        memory_path: canonicalize(&memory_path)?,
+    };
+    Ok((private_input, public_input))
+}
+
+/// Same as `prover_input_from_runner`, and also writes the inputs to `priv.json` and
+/// `pub.json` in `output_dir`, creating the directory if needed.
+pub fn write_prover_input(
+    runner: &CairoRunner,
+    output_dir: &Path,
+) -> Result<PrivateInput, ProverInputError> {
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| ProverInputError::Io(output_dir.to_path_buf(), e))?;
+    let (private_input, public_input) = prover_input_from_runner(runner, output_dir)?;
+
+    let priv_path = output_dir.join("priv.json");
+    std::fs::write(&priv_path, serde_json::to_string(&private_input)?)
+        .map_err(|e| ProverInputError::Io(priv_path, e))?;
+    let pub_path = output_dir.join("pub.json");
+    std::fs::write(&pub_path, serde_json::to_string(&public_input)?)
+        .map_err(|e| ProverInputError::Io(pub_path, e))?;
+
+    Ok(private_input)
+}
@@ -0,0 +1,152 @@
+//! Recursive bootloading: verifying the proof of a previous bootloader run in a task.
+//!
+//! The verified run is described by its output and the fact topologies of its tasks. The task
+//! running the Cairo verifier on its proof gets a `CompositePackedOutput` built from them, so
+//! that the bootloader unpacks the outputs of the inner tasks into its own output instead of the
+//! output of the verifier.
+
+use std::path::{Path, PathBuf};
+
+use cairo_bootloader_hints::bootloader_output::{
+    BootloaderOutput, BootloaderOutputError, TASK_OUTPUT_HEADER_SIZE,
+};
+use cairo_bootloader_hints::fact_topologies::{
+    read_fact_topologies_file, FactTopology, ReadFactTopologiesError,
+};
+use cairo_bootloader_hints::facts::single_page_fact_topology;
+use cairo_bootloader_hints::types::{CompositePackedOutput, PackedOutput};
+use cairo_vm::Felt252;
+use serde::Deserialize;
+
+#[derive(thiserror::Error, Debug)]
+pub enum RecursionError {
+    #[error("Failed to read {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Invalid program output {0}: {1}")]
+    Json(PathBuf, serde_json::Error),
+
+    #[error("Failed to read fact topologies: {0}")]
+    FactTopologies(#[from] ReadFactTopologiesError),
+
+    #[error("Invalid output of the verified run: {0}")]
+    Output(#[from] BootloaderOutputError),
+
+    #[error("The verified run has {n_tasks} tasks but {n_subtasks} packed outputs")]
+    WrongNumberOfSubtasks { n_tasks: usize, n_subtasks: usize },
+
+    #[error("The verified run has {n_tasks} tasks but {n_fact_topologies} fact topologies")]
+    WrongNumberOfFactTopologies {
+        n_tasks: usize,
+        n_fact_topologies: usize,
+    },
+
+    #[error("Task {task_index} of the verified run: fact topology covers {expected} words, but the task output has {actual}")]
+    OutputSizeMismatch {
+        task_index: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// A previous bootloader run whose proof is verified by one of the tasks.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VerifiedRun {
+    /// Index of the task running the Cairo verifier on the proof of the run.
+    pub task: usize,
+    /// Output of the run, as written by `run --program-output`.
+    pub program_output: PathBuf,
+    /// Fact topologies of the tasks of the run, as written to its `fact_topologies_path`. The
+    /// output of each task is assumed to be a single page if omitted.
+    #[serde(default)]
+    pub fact_topologies: Option<PathBuf>,
+}
+
+impl VerifiedRun {
+    /// Makes the paths relative to `base_dir`.
+    pub(crate) fn resolve_paths(&mut self, base_dir: &Path) {
+        for path in std::iter::once(&mut self.program_output).chain(&mut self.fact_topologies) {
+            if path.is_relative() {
+                *path = base_dir.join(&*path);
+            }
+        }
+    }
+
+    /// Reads the files of the run and builds the packed output of the verifier task.
+    pub fn load(&self) -> Result<CompositePackedOutput, RecursionError> {
+        let file = std::fs::File::open(&self.program_output)
+            .map_err(|e| RecursionError::Io(self.program_output.clone(), e))?;
+        let output: Vec<Felt252> = serde_json::from_reader(std::io::BufReader::new(file))
+            .map_err(|e| RecursionError::Json(self.program_output.clone(), e))?;
+        let fact_topologies = self
+            .fact_topologies
+            .as_deref()
+            .map(read_fact_topologies_file)
+            .transpose()?;
+
+        composite_packed_output(output, None, fact_topologies)
+    }
+}
+
+/// Builds the packed output of a task verifying a (simple) bootloader run.
+///
+/// * `output`: Output of the verified run.
+/// * `subtasks`: Packed outputs of the tasks of the verified run. Defaults to plain outputs.
+/// * `fact_topologies`: Fact topologies of the tasks of the verified run. Defaults to a single
+///   page per task.
+pub fn composite_packed_output(
+    output: Vec<Felt252>,
+    subtasks: Option<Vec<PackedOutput>>,
+    fact_topologies: Option<Vec<FactTopology>>,
+) -> Result<CompositePackedOutput, RecursionError> {
+    let tasks = BootloaderOutput::decode(&output)?.tasks;
+    let n_tasks = tasks.len();
+
+    let subtasks = subtasks.unwrap_or_else(|| vec![PackedOutput::Plain(vec![]); n_tasks]);
+    if subtasks.len() != n_tasks {
+        return Err(RecursionError::WrongNumberOfSubtasks {
+            n_tasks,
+            n_subtasks: subtasks.len(),
+        });
+    }
+
+    let fact_topologies = fact_topologies.unwrap_or_else(|| {
+        tasks
+            .iter()
+            .map(|task| single_page_fact_topology(task.size() - TASK_OUTPUT_HEADER_SIZE))
+            .collect()
+    });
+    if fact_topologies.len() != n_tasks {
+        return Err(RecursionError::WrongNumberOfFactTopologies {
+            n_tasks,
+            n_fact_topologies: fact_topologies.len(),
+        });
+    }
+
+    // The fact topologies of composite subtasks are replaced by the ones of their own subtasks,
+    // only check the plain ones.
+    for (task_index, (task, (subtask, fact_topology))) in tasks
+        .iter()
+        .zip(subtasks.iter().zip(&fact_topologies))
+        .enumerate()
+    {
+        if let PackedOutput::Plain(_) = subtask {
+            let output_size = task.size() - TASK_OUTPUT_HEADER_SIZE;
+            let topology_size: usize = fact_topology.page_sizes.iter().sum();
+            if topology_size != output_size {
+                return Err(RecursionError::OutputSizeMismatch {
+                    task_index,
+                    expected: topology_size,
+                    actual: output_size,
+                });
+            }
+        }
+    }
+
+    Ok(CompositePackedOutput {
+        outputs: output,
+        subtasks,
+        fact_topologies,
+    })
+}
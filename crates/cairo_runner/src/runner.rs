@@ -0,0 +1,422 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+use cairo_bootloader_hints::bootloader_output::{BootloaderOutput, BootloaderOutputError};
+use cairo_bootloader_hints::types::{BootloaderInput, TaskSpec};
+use cairo_bootloader_hints::{DebugMessage, DebugSink, HintImpl, LogLevel};
+use cairo_vm::air_public_input::PublicInput;
+use cairo_vm::cairo_run::{cairo_run_program_with_initial_scope, CairoRunConfig};
+use cairo_vm::types::errors::program_errors::ProgramError;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::types::layout::CairoLayoutParams;
+use cairo_vm::types::layout_name::LayoutName;
+use cairo_vm::types::program::Program;
+use cairo_vm::types::relocatable::Relocatable;
+use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
+use cairo_vm::vm::errors::memory_errors::MemoryError;
+use cairo_vm::vm::errors::runner_errors::RunnerError;
+use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
+use cairo_vm::vm::runners::cairo_runner::{CairoRunner, ExecutionResources};
+use cairo_vm::Felt252;
+
+use crate::bootloaders::{load_bootloader, BootloaderBuild};
+use crate::hint_processor::{BootloaderHintProcessor, PanicTraceback};
+use crate::manifest::{Manifest, ManifestError};
+use crate::prover_input::{
+    prover_input_from_runner, write_prover_input, PrivateInput, ProverInputError,
+};
+use crate::task::{make_bootloader_tasks, BootloaderTaskError};
+use crate::{insert_bootloader_input, insert_bootloader_program_identifiers};
+
+#[derive(thiserror::Error, Debug)]
+pub enum BootloaderRunError {
+    #[error(transparent)]
+    Task(#[from] BootloaderTaskError),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error("Failed to load the bootloader program: {0}")]
+    Bootloader(#[from] ProgramError),
+
+    #[error("The bootloader program has hints that are not implemented:\n{}", .0.join("\n---\n"))]
+    UnsupportedHints(Vec<String>),
+
+    #[error(transparent)]
+    Run(#[from] CairoRunError),
+
+    #[error("{traceback}{source}")]
+    TaskPanic {
+        traceback: PanicTraceback,
+        source: Box<CairoRunError>,
+    },
+
+    #[error("Failed to read the bootloader output: {0}")]
+    Output(#[from] VirtualMachineError),
+
+    #[error("Failed to read the bootloader output: {0}")]
+    OutputMemory(#[from] MemoryError),
+
+    #[error("Failed to get execution resources: {0}")]
+    ExecutionResources(#[from] RunnerError),
+
+    #[error(transparent)]
+    ProverInput(#[from] ProverInputError),
+
+    #[error("Unknown layout: {0}")]
+    UnknownLayout(String),
+
+    #[error("The dynamic layout requires layout params")]
+    MissingDynamicLayoutParams,
+
+    #[error("Layout params can only be used with the dynamic layout, got {0}")]
+    UnexpectedDynamicLayoutParams(&'static str),
+
+    #[error("The {0} layout cannot run the bootloader: {1}")]
+    UnsupportedLayout(&'static str, RunnerError),
+}
+
+/// All the layouts known to the Cairo VM.
+const LAYOUTS: [LayoutName; 12] = [
+    LayoutName::plain,
+    LayoutName::small,
+    LayoutName::dex,
+    LayoutName::recursive,
+    LayoutName::starknet,
+    LayoutName::starknet_with_keccak,
+    LayoutName::recursive_large_output,
+    LayoutName::recursive_with_poseidon,
+    LayoutName::all_solidity,
+    LayoutName::all_cairo,
+    LayoutName::all_cairo_stwo,
+    LayoutName::dynamic,
+];
+
+/// Parses a layout from its name, e.g. `starknet_with_keccak`.
+pub fn parse_layout(name: &str) -> Result<LayoutName, BootloaderRunError> {
+    LAYOUTS
+        .into_iter()
+        .find(|layout| layout.to_str() == name)
+        .ok_or_else(|| BootloaderRunError::UnknownLayout(name.to_string()))
+}
+
+/// Options of the Cairo VM run.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub layout: LayoutName,
+    /// Params of the `dynamic` layout. Required for it, and rejected for other layouts.
+    pub dynamic_layout_params: Option<CairoLayoutParams>,
+    /// Run in proof mode. Disable for faster dry runs that do not produce prover input.
+    pub proof_mode: bool,
+    /// Do not pad the trace to the next power of two.
+    pub disable_trace_padding: bool,
+    /// Run the Cairo VM security checks at the end of the run. Defaults to the VM's choice when
+    /// unset.
+    pub secure_run: Option<bool>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            layout: LayoutName::all_cairo_stwo,
+            dynamic_layout_params: None,
+            proof_mode: true,
+            disable_trace_padding: true,
+            secure_run: None,
+        }
+    }
+}
+
+impl RunOptions {
+    /// Loads the `dynamic` layout params from a JSON file, as produced by `cairo-run`.
+    pub fn with_dynamic_layout_params_file(mut self, path: &Path) -> std::io::Result<Self> {
+        self.dynamic_layout_params = Some(CairoLayoutParams::from_file(path)?);
+        Ok(self)
+    }
+
+    /// Checks that the layout can run `program`, i.e. that it is consistent with its params
+    /// and that it has an instance of every builtin declared by the program.
+    pub fn validate(&self, program: &Program) -> Result<(), BootloaderRunError> {
+        match (self.layout, &self.dynamic_layout_params) {
+            (LayoutName::dynamic, None) => {
+                return Err(BootloaderRunError::MissingDynamicLayoutParams)
+            }
+            (LayoutName::dynamic, Some(_)) | (_, None) => {}
+            (layout, Some(_)) => {
+                return Err(BootloaderRunError::UnexpectedDynamicLayoutParams(
+                    layout.to_str(),
+                ))
+            }
+        }
+
+        let unsupported_layout = |e| BootloaderRunError::UnsupportedLayout(self.layout.to_str(), e);
+        let mut runner = CairoRunner::new(
+            program,
+            self.layout,
+            self.dynamic_layout_params.clone(),
+            self.proof_mode,
+            false,
+            self.disable_trace_padding,
+        )
+        .map_err(unsupported_layout)?;
+        runner
+            .initialize_builtins(false)
+            .map_err(unsupported_layout)?;
+
+        Ok(())
+    }
+}
+
+/// Runs the bootloader on a set of tasks.
+///
+/// ```no_run
+/// # use cairo_runner::manifest::Manifest;
+/// # use cairo_runner::runner::BootloaderRunner;
+/// let manifest = Manifest::from_tasks(vec!["examples/noop.zip".parse().unwrap()]);
+/// let result = BootloaderRunner::from_manifest(&manifest)
+///     .unwrap()
+///     .run()
+///     .unwrap();
+/// println!("{:?}", result.output);
+/// ```
+pub struct BootloaderRunner {
+    bootloader_program: Option<Program>,
+    bootloader_input: BootloaderInput,
+    cairo0_hints: HashMap<String, HintImpl>,
+    debug_sink: Option<Box<dyn DebugSink>>,
+    log_level: LogLevel,
+    run_options: RunOptions,
+}
+
+impl BootloaderRunner {
+    /// Creates a runner for the given input, using the embedded bootloader and the default
+    /// `RunOptions`.
+    pub fn new(bootloader_input: BootloaderInput) -> Self {
+        Self {
+            bootloader_program: None,
+            bootloader_input,
+            cairo0_hints: HashMap::new(),
+            debug_sink: None,
+            log_level: LogLevel::default(),
+            run_options: RunOptions::default(),
+        }
+    }
+
+    /// Loads the tasks of the manifest and creates a runner for them.
+    pub fn from_manifest(manifest: &Manifest) -> Result<Self, BootloaderRunError> {
+        let tasks = make_bootloader_tasks(&manifest.tasks)?;
+        let bootloader_input = manifest.bootloader_input(tasks)?;
+
+        Ok(Self::new(bootloader_input))
+    }
+
+    /// Runs this bootloader program instead of the embedded one.
+    pub fn with_bootloader_program(mut self, bootloader_program: Program) -> Self {
+        self.bootloader_program = Some(bootloader_program);
+        self
+    }
+
+    /// Runs one of the embedded bootloaders instead of the default one.
+    pub fn with_bootloader_build(
+        self,
+        bootloader_build: &BootloaderBuild,
+    ) -> Result<Self, BootloaderRunError> {
+        tracing::debug!(bootloader = %bootloader_build, "Loading the bootloader");
+        Ok(self.with_bootloader_program(bootloader_build.load()?))
+    }
+
+    /// Registers the implementation of a hint of a Cairo 0 task, keyed by its code. The
+    /// program input of the task is available in the `program_input` scope variable.
+    pub fn with_cairo0_hint(mut self, hint_code: impl Into<String>, hint_impl: HintImpl) -> Self {
+        self.cairo0_hints.insert(hint_code.into(), hint_impl);
+        self
+    }
+
+    /// Sends the messages of the `print_*`, `info_*` and `debug_*` hints to `debug_sink`.
+    /// They are forwarded to `tracing` by default. Use a `CollectSink` to get them in
+    /// `BootloaderRunResult::debug_messages`, or a `NullSink` to drop them.
+    pub fn with_debug_sink(mut self, debug_sink: impl DebugSink + 'static) -> Self {
+        self.debug_sink = Some(Box::new(debug_sink));
+        self
+    }
+
+    /// Sets the level of the print hints. At the default `Info` level, `debug_*` hints are
+    /// no-ops.
+    pub fn with_log_level(mut self, log_level: LogLevel) -> Self {
+        self.log_level = log_level;
+        self
+    }
+
+    pub fn with_run_options(mut self, run_options: RunOptions) -> Self {
+        self.run_options = run_options;
+        self
+    }
+
+    pub fn with_layout(mut self, layout: LayoutName) -> Self {
+        self.run_options.layout = layout;
+        self
+    }
+
+    pub fn with_proof_mode(mut self, proof_mode: bool) -> Self {
+        self.run_options.proof_mode = proof_mode;
+        self
+    }
+
+    /// Runs the bootloader to completion.
+    pub fn run(self) -> Result<BootloaderRunResult, BootloaderRunError> {
+        let bootloader_program = match self.bootloader_program {
+            Some(program) => program,
+            None => load_bootloader()?,
+        };
+        let run_options = self.run_options;
+        run_options.validate(&bootloader_program)?;
+
+        let _run = tracing::info_span!(
+            "bootloader_run",
+            layout = run_options.layout.to_str(),
+            proof_mode = run_options.proof_mode,
+            n_tasks = self.bootloader_input.simple_bootloader_input.tasks.len(),
+        )
+        .entered();
+
+        let mut hint_processor = BootloaderHintProcessor::new();
+        for (hint_code, hint_impl) in self.cairo0_hints {
+            hint_processor.add_hint(hint_code, hint_impl);
+        }
+        if let Some(debug_sink) = self.debug_sink {
+            hint_processor.set_debug_sink(debug_sink);
+        }
+        hint_processor.set_log_level(self.log_level);
+
+        // Fail before running any task if a hint of the bootloader is missing. Tasks may not
+        // reach all their hints, so theirs are only reported. Cairo PIEs run without hints.
+        let unsupported_hints =
+            hint_processor.unsupported_hints(&bootloader_program, &HashMap::new());
+        if !unsupported_hints.is_empty() {
+            return Err(BootloaderRunError::UnsupportedHints(unsupported_hints));
+        }
+        for (task_id, task) in self
+            .bootloader_input
+            .simple_bootloader_input
+            .tasks
+            .iter()
+            .enumerate()
+        {
+            if let TaskSpec::RunProgram(task) = task {
+                for hint_code in hint_processor.unsupported_hints(&task.program, &task.hints) {
+                    tracing::warn!(task_id, %hint_code, "Task hint is not implemented");
+                }
+            }
+        }
+
+        let cairo_run_config = CairoRunConfig {
+            entrypoint: "main",
+            trace_enabled: true,
+            relocate_mem: true,
+            layout: run_options.layout,
+            proof_mode: run_options.proof_mode,
+            secure_run: run_options.secure_run,
+            disable_trace_padding: run_options.disable_trace_padding,
+            allow_missing_builtins: None,
+            dynamic_layout_params: run_options.dynamic_layout_params,
+            ..Default::default()
+        };
+
+        let mut exec_scopes = ExecutionScopes::new();
+        insert_bootloader_input(&mut exec_scopes, self.bootloader_input);
+        insert_bootloader_program_identifiers(&mut exec_scopes, &bootloader_program);
+
+        let mut runner = cairo_run_program_with_initial_scope(
+            &bootloader_program,
+            &cairo_run_config,
+            &mut hint_processor,
+            exec_scopes,
+        )
+        .map_err(|error| match hint_processor.take_panic_traceback() {
+            Some(traceback) => BootloaderRunError::TaskPanic {
+                traceback,
+                source: Box::new(error),
+            },
+            None => error.into(),
+        })?;
+
+        let output = read_output(&mut runner)?;
+        let execution_resources = runner.get_execution_resources()?;
+        tracing::info!(
+            n_steps = execution_resources.n_steps,
+            output_size = output.len(),
+            "Bootloader run finished"
+        );
+
+        Ok(BootloaderRunResult {
+            runner,
+            output,
+            execution_resources,
+            debug_messages: hint_processor.take_debug_messages(),
+        })
+    }
+}
+
+/// Runs the tasks of the manifest with the default options.
+pub fn run_bootloader(manifest: &Manifest) -> Result<BootloaderRunResult, BootloaderRunError> {
+    BootloaderRunner::from_manifest(manifest)?.run()
+}
+
+/// Reads the content of the output builtin segment.
+fn read_output(runner: &mut CairoRunner) -> Result<Vec<Felt252>, BootloaderRunError> {
+    let output_base = runner.vm.get_output_builtin_mut()?.base();
+    let output_size = runner
+        .vm
+        .segments
+        .compute_effective_sizes()
+        .get(output_base)
+        .copied()
+        .unwrap_or_default();
+    let output = runner
+        .vm
+        .get_integer_range(Relocatable::from((output_base as isize, 0)), output_size)?;
+
+    Ok(output.into_iter().map(Cow::into_owned).collect())
+}
+
+/// A finished bootloader run.
+pub struct BootloaderRunResult {
+    pub runner: CairoRunner,
+    /// Content of the output builtin segment.
+    pub output: Vec<Felt252>,
+    pub execution_resources: ExecutionResources,
+    /// Messages of the print hints, if the run was configured with a sink that keeps them.
+    pub debug_messages: Vec<DebugMessage>,
+}
+
+impl BootloaderRunResult {
+    /// Decodes the output into the outputs of the individual tasks.
+    pub fn bootloader_output(&self) -> Result<BootloaderOutput, BootloaderOutputError> {
+        BootloaderOutput::decode(&self.output)
+    }
+
+    /// The messages printed by a task, in order.
+    pub fn task_debug_messages(&self, task_id: usize) -> impl Iterator<Item = &DebugMessage> {
+        self.debug_messages
+            .iter()
+            .filter(move |message| message.task_id == Some(task_id))
+    }
+
+    /// Writes the encoded trace and memory to `output_dir` and returns the prover inputs.
+    pub fn prover_input(
+        &self,
+        output_dir: &Path,
+    ) -> Result<(PrivateInput, PublicInput<'_>), BootloaderRunError> {
+        Ok(prover_input_from_runner(&self.runner, output_dir)?)
+    }
+
+    /// Writes the encoded trace and memory along with `priv.json` and `pub.json` to
+    /// `output_dir`.
+    pub fn write_prover_input(
+        &self,
+        output_dir: &Path,
+    ) -> Result<PrivateInput, BootloaderRunError> {
+        Ok(write_prover_input(&self.runner, output_dir)?)
+    }
+}
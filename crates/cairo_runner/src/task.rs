@@ -1,6 +1,9 @@
-use cairo_bootloader_hints::types::{CairoPieTask, RunProgramTask, TaskSpec};
+use cairo_bootloader_hints::types::{
+    CairoPiePath, CairoPieTask, RunProgramTask, TaskSpec, UserArg,
+};
 use cairo_lang_casm::hints::Hint;
 use cairo_lang_executable::executable::{EntryPointKind, Executable, ExecutableEntryPoint};
+use cairo_lang_execute_utils::user_args_from_flags;
 use cairo_lang_runner::{build_hints_dict, Arg, CairoHintProcessor};
 use cairo_vm::types::errors::program_errors::ProgramError;
 use cairo_vm::types::program::Program;
@@ -9,7 +12,8 @@ use cairo_vm::types::relocatable::MaybeRelocatable;
 use cairo_vm::vm::runners::cairo_pie::CairoPie;
 use cairo_vm::Felt252;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 #[derive(thiserror::Error, Debug)]
 pub enum BootloaderTaskError {
@@ -18,49 +22,503 @@ pub enum BootloaderTaskError {
 
     #[error("Failed to read PIE: {0}")]
     Pie(#[from] std::io::Error),
+
+    #[error("Failed to read user args from {0}: {1}")]
+    UserArgs(PathBuf, String),
+
+    #[error("Failed to read program input from {0}: {1}")]
+    ProgramInput(PathBuf, String),
+
+    #[error("Failed to read {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+
+    #[error("Invalid Cairo 1 executable {0}: {1}")]
+    InvalidExecutable(PathBuf, serde_json::Error),
+
+    #[error("Cairo PIE tasks do not take an input file: {0}")]
+    UnexpectedInputFile(PathBuf),
+
+    #[error("{path} has no {kind} entrypoint")]
+    NoEntrypoint { path: PathBuf, kind: &'static str },
+
+    #[error("{path} has {n_entrypoints} entrypoints, there is no entrypoint {index}")]
+    EntrypointOutOfBounds {
+        path: PathBuf,
+        index: usize,
+        n_entrypoints: usize,
+    },
+
+    #[error(
+        "Entrypoint {index} of {path} is a standalone entrypoint, which cannot be bootloaded: it \
+         initializes its own builtins and ends in an infinite loop instead of returning to the \
+         bootloader. Use a bootloader entrypoint of the executable instead"
+    )]
+    StandaloneEntrypoint { path: PathBuf, index: usize },
+
+    #[error("Only Cairo 1 executables have entrypoints: {0}")]
+    UnexpectedEntrypoint(PathBuf),
+
+    #[error("Task {index} ({path}): {source}")]
+    Task {
+        index: usize,
+        path: PathBuf,
+        source: Box<BootloaderTaskError>,
+    },
+
+    #[error(
+        "{0} is neither a compiled Cairo 0 program, a Cairo 1 executable nor a Cairo PIE: {1}"
+    )]
+    UnknownFormat(PathBuf, &'static str),
 }
 
-pub fn make_bootloader_tasks(
-    program: &Path,
-    program_input: Option<serde_json::Value>,
-) -> Result<(TaskSpec, HashMap<String, Hint>), BootloaderTaskError> {
-    // let program = get_program_from_executable(program);
-    let executable: Executable = serde_json::from_reader(std::fs::File::open(program).unwrap())
-        .expect("Failed to read executable");
+/// The kinds of files a program can be read from.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramKind {
+    /// A compiled Cairo 0 program (`cairo-compile` output).
+    Cairo0,
+    /// A Cairo 1 executable, run through its bootloader entrypoint.
+    Executable,
+    /// A Cairo PIE zip.
+    CairoPie,
+}
 
-    let entrypoint = executable
-        .entrypoints
-        .iter()
-        .find(|e| matches!(e.kind, EntryPointKind::Bootloader))
-        .expect("Failed to find entrypoint");
+impl ProgramKind {
+    /// Detects the kind of the file at `path` from its content: a zip archive is a Cairo PIE, a
+    /// JSON object with `entrypoints` is a Cairo 1 executable and one with `data` and `prime` is
+    /// a compiled Cairo 0 program.
+    pub fn detect(path: &Path) -> Result<Self, BootloaderTaskError> {
+        const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+        let content =
+            std::fs::read(path).map_err(|e| BootloaderTaskError::Read(path.to_path_buf(), e))?;
+        if content.starts_with(ZIP_MAGIC) {
+            return Ok(ProgramKind::CairoPie);
+        }
 
-    let (program, string_to_hint) = program_and_hints_from_executable(&executable, entrypoint);
+        let unknown_format =
+            |reason| BootloaderTaskError::UnknownFormat(path.to_path_buf(), reason);
+        let json: serde_json::Value =
+            serde_json::from_slice(&content).map_err(|_| unknown_format("not a zip nor JSON"))?;
+        let object = json
+            .as_object()
+            .ok_or_else(|| unknown_format("not a JSON object"))?;
 
-    let program_input_map = if let Some(input) = program_input {
-        if let serde_json::Value::Object(map) = input {
-            map.into_iter()
-                .map(|(k, v)| (k, v))
-                .collect::<HashMap<String, serde_json::Value>>()
+        if object.contains_key("entrypoints") && object.contains_key("program") {
+            Ok(ProgramKind::Executable)
+        } else if object.contains_key("data") && object.contains_key("prime") {
+            Ok(ProgramKind::Cairo0)
         } else {
-            HashMap::new()
+            Err(unknown_format("unexpected JSON fields"))
+        }
+    }
+}
+
+/// Selects the entrypoint of a Cairo 1 executable to run.
+#[derive(serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(try_from = "EntrypointSelectorRepr")]
+pub enum EntrypointSelector {
+    /// The first bootloader entrypoint.
+    #[default]
+    Bootloader,
+    /// The first standalone entrypoint. Standalone entrypoints cannot be bootloaded, this only
+    /// exists to report why.
+    Standalone,
+    /// The entrypoint at this index in the executable.
+    Index(usize),
+}
+
+/// Selectors are written as an index or a kind name.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum EntrypointSelectorRepr {
+    Index(usize),
+    Kind(String),
+}
+
+impl TryFrom<EntrypointSelectorRepr> for EntrypointSelector {
+    type Error = String;
+
+    fn try_from(value: EntrypointSelectorRepr) -> Result<Self, Self::Error> {
+        match value {
+            EntrypointSelectorRepr::Index(index) => Ok(EntrypointSelector::Index(index)),
+            EntrypointSelectorRepr::Kind(kind) => kind.parse(),
+        }
+    }
+}
+
+impl FromStr for EntrypointSelector {
+    type Err = String;
+
+    /// Parses `bootloader`, `standalone` or an entrypoint index.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bootloader" => Ok(EntrypointSelector::Bootloader),
+            "standalone" => Ok(EntrypointSelector::Standalone),
+            _ => s.parse().map(EntrypointSelector::Index).map_err(|_| {
+                format!("Invalid entrypoint {s}, expected bootloader, standalone or an index")
+            }),
+        }
+    }
+}
+
+impl EntrypointSelector {
+    /// Returns the index of the selected entrypoint of `executable`, read from `path`. Fails if
+    /// the entrypoint does not exist or cannot be bootloaded.
+    pub fn select(
+        self,
+        executable: &Executable,
+        path: &Path,
+    ) -> Result<usize, BootloaderTaskError> {
+        let first_of_kind = |is_standalone: bool, kind_name| {
+            executable
+                .entrypoints
+                .iter()
+                .position(|e| matches!(e.kind, EntryPointKind::Standalone) == is_standalone)
+                .ok_or_else(|| BootloaderTaskError::NoEntrypoint {
+                    path: path.to_path_buf(),
+                    kind: kind_name,
+                })
+        };
+        let index = match self {
+            EntrypointSelector::Bootloader => first_of_kind(false, "bootloader")?,
+            EntrypointSelector::Standalone => first_of_kind(true, "standalone")?,
+            EntrypointSelector::Index(index) => index,
+        };
+
+        let entrypoint = executable.entrypoints.get(index).ok_or_else(|| {
+            BootloaderTaskError::EntrypointOutOfBounds {
+                path: path.to_path_buf(),
+                index,
+                n_entrypoints: executable.entrypoints.len(),
+            }
+        })?;
+        if matches!(entrypoint.kind, EntryPointKind::Standalone) {
+            return Err(BootloaderTaskError::StandaloneEntrypoint {
+                path: path.to_path_buf(),
+                index,
+            });
+        }
+
+        Ok(index)
+    }
+}
+
+/// Where to load a bootloader task from.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TaskSource {
+    /// A Cairo 1 executable, and optionally a file holding its user args in the format
+    /// expected by `cairo-execute --args-file`. Runs the first bootloader entrypoint unless
+    /// another one is selected.
+    Executable {
+        path: PathBuf,
+        #[serde(default)]
+        args_file: Option<PathBuf>,
+        #[serde(default)]
+        entrypoint: EntrypointSelector,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+    /// A compiled Cairo 0 program, and optionally a JSON file holding its program input. The
+    /// input is exposed as `program_input` to the hints of the program.
+    Cairo0 {
+        path: PathBuf,
+        #[serde(default)]
+        program_input: Option<PathBuf>,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+    /// A Cairo PIE zip file.
+    CairoPie {
+        path: PathBuf,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+    /// A file whose kind is detected from its content with `ProgramKind::detect`. The input
+    /// file holds the user args of a Cairo 1 executable, or the program input of a Cairo 0
+    /// program.
+    Auto {
+        path: PathBuf,
+        #[serde(default)]
+        input_file: Option<PathBuf>,
+        /// Entrypoint to run, if the file is a Cairo 1 executable.
+        #[serde(default)]
+        entrypoint: Option<EntrypointSelector>,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+}
+
+impl TaskSource {
+    /// Path of the program, executable or PIE.
+    pub fn path(&self) -> &Path {
+        match self {
+            TaskSource::Executable { path, .. }
+            | TaskSource::Cairo0 { path, .. }
+            | TaskSource::CairoPie { path, .. }
+            | TaskSource::Auto { path, .. } => path,
         }
-    } else {
-        HashMap::new()
-    };
+    }
+
+    /// Path of the file holding the user args or the program input of the task, if any.
+    pub fn input_path(&self) -> Option<&Path> {
+        match self {
+            TaskSource::Executable { args_file, .. } => args_file.as_deref(),
+            TaskSource::Cairo0 { program_input, .. } => program_input.as_deref(),
+            TaskSource::CairoPie { .. } => None,
+            TaskSource::Auto { input_file, .. } => input_file.as_deref(),
+        }
+    }
+
+    /// Resolves `Auto` sources to the detected kind. Other sources are returned as is.
+    pub fn detect(self) -> Result<Self, BootloaderTaskError> {
+        let TaskSource::Auto {
+            path,
+            input_file,
+            entrypoint,
+            use_poseidon,
+        } = self
+        else {
+            return Ok(self);
+        };
+
+        let kind = ProgramKind::detect(&path)?;
+        if entrypoint.is_some() && kind != ProgramKind::Executable {
+            return Err(BootloaderTaskError::UnexpectedEntrypoint(path));
+        }
+
+        Ok(match kind {
+            ProgramKind::Cairo0 => TaskSource::Cairo0 {
+                path,
+                program_input: input_file,
+                use_poseidon,
+            },
+            ProgramKind::Executable => TaskSource::Executable {
+                path,
+                args_file: input_file,
+                entrypoint: entrypoint.unwrap_or_default(),
+                use_poseidon,
+            },
+            ProgramKind::CairoPie => {
+                if input_file.is_some() {
+                    return Err(BootloaderTaskError::UnexpectedInputFile(path));
+                }
+                TaskSource::CairoPie { path, use_poseidon }
+            }
+        })
+    }
+}
+
+impl FromStr for TaskSource {
+    type Err = String;
+
+    /// Parses `[<kind>:]<path>[@<entrypoint>][,<input_file>]`, `<kind>` being one of `cairo0`,
+    /// `executable` or `cairo_pie`. Without a kind, the kind is detected from the content of the
+    /// file when the task is loaded. The entrypoint of a Cairo 1 executable is parsed with
+    /// `EntrypointSelector::from_str`. The input file is the program input of a Cairo 0 program,
+    /// or the user args of a Cairo 1 executable.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, s) = match s.split_once(':') {
+            Some(("cairo0", rest)) => (Some(ProgramKind::Cairo0), rest),
+            Some(("executable", rest)) => (Some(ProgramKind::Executable), rest),
+            Some(("cairo_pie", rest)) => (Some(ProgramKind::CairoPie), rest),
+            _ => (None, s),
+        };
+        let (path, input_file) = match s.split_once(',') {
+            Some((path, input_file)) => (path, Some(PathBuf::from(input_file))),
+            None => (s, None),
+        };
+        // `@` may be part of the path, only strip it if it is followed by a valid entrypoint.
+        let selected_entrypoint = path.rsplit_once('@').and_then(|(path, entrypoint)| {
+            Some((path, entrypoint.parse::<EntrypointSelector>().ok()?))
+        });
+        let (path, entrypoint) = match selected_entrypoint {
+            Some((path, entrypoint)) => (PathBuf::from(path), Some(entrypoint)),
+            None => (PathBuf::from(path), None),
+        };
+        if entrypoint.is_some() && matches!(kind, Some(kind) if kind != ProgramKind::Executable) {
+            return Err(format!(
+                "Only Cairo 1 executables have entrypoints: {}",
+                path.display()
+            ));
+        }
+
+        Ok(match kind {
+            Some(ProgramKind::Cairo0) => TaskSource::Cairo0 {
+                path,
+                program_input: input_file,
+                use_poseidon: false,
+            },
+            Some(ProgramKind::Executable) => TaskSource::Executable {
+                path,
+                args_file: input_file,
+                entrypoint: entrypoint.unwrap_or_default(),
+                use_poseidon: false,
+            },
+            Some(ProgramKind::CairoPie) => {
+                if input_file.is_some() {
+                    return Err(format!(
+                        "Cairo PIE tasks do not take an input file: {}",
+                        path.display()
+                    ));
+                }
+                TaskSource::CairoPie {
+                    path,
+                    use_poseidon: false,
+                }
+            }
+            None => TaskSource::Auto {
+                path,
+                input_file,
+                entrypoint,
+                use_poseidon: false,
+            },
+        })
+    }
+}
+
+/// Builds the bootloader tasks for a mixed list of Cairo 1 executables, Cairo 0 programs and
+/// Cairo PIEs, in order. Each Cairo 1 task carries its own hints and the user args of its own
+/// args file.
+pub fn make_bootloader_tasks(sources: &[TaskSource]) -> Result<Vec<TaskSpec>, BootloaderTaskError> {
+    sources
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+            make_task(source.clone()).map_err(|e| BootloaderTaskError::Task {
+                index,
+                path: source.path().to_path_buf(),
+                source: Box::new(e),
+            })
+        })
+        .collect()
+}
+
+fn make_task(source: TaskSource) -> Result<TaskSpec, BootloaderTaskError> {
+    Ok(match source {
+        TaskSource::Executable {
+            path,
+            args_file,
+            entrypoint,
+            use_poseidon,
+        } => {
+            let mut task = make_bootloader_task(&path, entrypoint)?;
+            if let TaskSpec::RunProgram(run_program_task) = &mut task {
+                run_program_task.use_poseidon = use_poseidon;
+                if let Some(args_file) = args_file {
+                    run_program_task.user_args = read_user_args(&args_file)?;
+                }
+            }
+            task
+        }
+        TaskSource::Cairo0 {
+            path,
+            program_input,
+            use_poseidon,
+        } => {
+            let program_input = program_input
+                .as_deref()
+                .map(read_program_input)
+                .transpose()?
+                .unwrap_or_default();
+            TaskSpec::RunProgram(RunProgramTask::new(
+                Program::from_file(&path, Some("main"))?,
+                program_input,
+                use_poseidon,
+            ))
+        }
+        TaskSource::CairoPie { path, use_poseidon } => {
+            TaskSpec::CairoPiePath(CairoPiePath { path, use_poseidon })
+        }
+        // `detect` resolves the kind of the task, it never returns `Auto`.
+        source @ TaskSource::Auto { .. } => return make_task(source.detect()?),
+    })
+}
+
+/// Builds the task for the selected entrypoint of a Cairo 1 executable. The task runs without
+/// user args, see `read_user_args`.
+pub fn make_bootloader_task(
+    program_path: &Path,
+    entrypoint: EntrypointSelector,
+) -> Result<TaskSpec, BootloaderTaskError> {
+    let (program, hints) = load_executable(program_path, entrypoint)?;
+
+    let task = RunProgramTask::new(program, HashMap::new(), false)
+        .with_hints(hints)
+        .with_user_args(task_user_args(vec![]));
+    Ok(TaskSpec::RunProgram(task))
+}
+
+/// Reads the user args of a Cairo 1 task from a JSON args file, as taken by `cairo-execute`.
+pub fn read_user_args(path: &Path) -> Result<Vec<Vec<UserArg>>, BootloaderTaskError> {
+    let args = user_args_from_flags(Some(&path.to_path_buf()), &[])
+        .map_err(|e| BootloaderTaskError::UserArgs(path.to_path_buf(), e.to_string()))?;
+    Ok(task_user_args(args))
+}
+
+/// The run params of a Cairo 1 task: the executable reads its arguments as a single array.
+fn task_user_args(args: Vec<Arg>) -> Vec<Vec<UserArg>> {
+    vec![vec![UserArg::Array(
+        args.into_iter().map(user_arg).collect(),
+    )]]
+}
+
+fn user_arg(arg: Arg) -> UserArg {
+    match arg {
+        Arg::Value(value) => UserArg::Value(value),
+        Arg::Array(values) => UserArg::Array(values.into_iter().map(user_arg).collect()),
+    }
+}
+
+/// Reads the program input of a task from a JSON file holding an object.
+pub fn read_program_input(
+    path: &Path,
+) -> Result<HashMap<String, serde_json::Value>, BootloaderTaskError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| BootloaderTaskError::ProgramInput(path.to_path_buf(), e.to_string()))?;
+    let input = serde_json::from_str(&content)
+        .map_err(|e| BootloaderTaskError::ProgramInput(path.to_path_buf(), e.to_string()))?;
+    match input {
+        serde_json::Value::Object(map) => Ok(map.into_iter().collect()),
+        _ => Err(BootloaderTaskError::ProgramInput(
+            path.to_path_buf(),
+            "program input must be a JSON object".to_string(),
+        )),
+    }
+}
+
+/// Reads a Cairo 1 executable.
+pub fn read_executable(path: &Path) -> Result<Executable, BootloaderTaskError> {
+    let file =
+        std::fs::File::open(path).map_err(|e| BootloaderTaskError::Read(path.to_path_buf(), e))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|e| BootloaderTaskError::InvalidExecutable(path.to_path_buf(), e))
+}
+
+/// Lists the entrypoints of a Cairo 1 executable, with the builtins they use.
+pub fn list_entrypoints(path: &Path) -> Result<Vec<ExecutableEntryPoint>, BootloaderTaskError> {
+    Ok(read_executable(path)?.entrypoints)
+}
+
+/// Reads a Cairo 1 executable and returns the program of the selected entrypoint, along with
+/// the hints it uses.
+pub fn load_executable(
+    path: &Path,
+    entrypoint: EntrypointSelector,
+) -> Result<(Program, HashMap<String, Hint>), BootloaderTaskError> {
+    let executable = read_executable(path)?;
+    let index = entrypoint.select(&executable, path)?;
 
-    let task = TaskSpec::RunProgram(RunProgramTask {
-        program,
-        // program_input: program_input.unwrap().into_object().unwrap(),
-        program_input: HashMap::new(),
-        use_poseidon: false,
-    });
-    Ok((task, string_to_hint))
+    Ok(program_and_hints_from_executable(
+        &executable,
+        &executable.entrypoints[index],
+    )?)
 }
 
 pub fn program_and_hints_from_executable(
     executable: &Executable,
     entrypoint: &ExecutableEntryPoint,
-) -> (Program, HashMap<String, Hint>) {
+) -> Result<(Program, HashMap<String, Hint>), ProgramError> {
     let data: Vec<MaybeRelocatable> = executable
         .program
         .bytecode
@@ -91,7 +549,7 @@ pub fn program_and_hints_from_executable(
             vec![],
             None,
         ),
-    };
+    }?;
 
-    (program.unwrap(), string_to_hint)
+    Ok((program, string_to_hint))
 }
@@ -0,0 +1,19 @@
+use cairo_bootloader_hints::execute_task_hints::{CALL_TASK_LABEL, RET_PC_LABEL};
+use cairo_bootloader_hints::types::ProgramIdentifiers;
+use cairo_bootloader_hints::vars::BOOTLOADER_PROGRAM_IDENTIFIERS;
+use cairo_runner::bootloaders::load_bootloader;
+use cairo_runner::insert_bootloader_program_identifiers;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+
+/// `call_task` computes the return pc of Cairo PIE tasks from these labels.
+#[test]
+fn test_bootloader_call_task_labels() {
+    let bootloader = load_bootloader().unwrap();
+    let mut exec_scopes = ExecutionScopes::new();
+    insert_bootloader_program_identifiers(&mut exec_scopes, &bootloader);
+
+    let identifiers: ProgramIdentifiers = exec_scopes.get(BOOTLOADER_PROGRAM_IDENTIFIERS).unwrap();
+    let call_task = identifiers[CALL_TASK_LABEL].pc.unwrap();
+    let ret_pc_label = identifiers[RET_PC_LABEL].pc.unwrap();
+    assert!(ret_pc_label > call_task);
+}
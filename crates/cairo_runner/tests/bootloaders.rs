@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use cairo_runner::bootloaders::{find_bootloader, BootloaderKind, BOOTLOADER_BUILDS};
+use cairo_runner::hint_processor::BootloaderHintProcessor;
+
+#[test]
+fn test_bootloader_builds_cairo_lang_version() {
+    for build in BOOTLOADER_BUILDS {
+        assert_eq!(
+            build.detect_cairo_lang_version().as_deref(),
+            Some(build.cairo_lang_version),
+            "{build}"
+        );
+    }
+}
+
+#[test]
+fn test_bootloader_builds_hints_are_supported() {
+    let hint_processor = BootloaderHintProcessor::new();
+    for build in BOOTLOADER_BUILDS {
+        let unsupported_hints =
+            hint_processor.unsupported_hints(&build.load().unwrap(), &HashMap::new());
+        assert!(
+            unsupported_hints.is_empty(),
+            "{build}: {unsupported_hints:#?}"
+        );
+    }
+}
+
+#[test]
+fn test_find_bootloader() {
+    let build = find_bootloader(BootloaderKind::Stwo, None).unwrap();
+    assert_eq!(build.kind, BootloaderKind::Stwo);
+
+    let build = find_bootloader(BootloaderKind::Stwo, Some(build.cairo_lang_version)).unwrap();
+    assert_eq!(build.kind, BootloaderKind::Stwo);
+
+    assert!(find_bootloader(BootloaderKind::Stwo, Some("0.0.0")).is_err());
+}
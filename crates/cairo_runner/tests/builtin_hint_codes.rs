@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use cairo_runner::builtin_hint_codes::{BUILTIN_HINT_CODES, CAIRO_VM_REVISION};
+use cairo_vm::any_box;
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::{
+    BuiltinHintProcessor, HintProcessorData,
+};
+use cairo_vm::hint_processor::hint_processor_definition::HintProcessorLogic;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+
+#[test]
+fn test_builtin_hint_codes_are_implemented() {
+    // The hints are run on an empty VM: unknown hints fail with `UnknownHint` before anything
+    // else, known ones fail on their missing variables or succeed.
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    for code in BUILTIN_HINT_CODES {
+        let hint_data = any_box!(HintProcessorData::new_default(
+            code.to_string(),
+            HashMap::new()
+        ));
+        let result = hint_processor.execute_hint(
+            &mut VirtualMachine::new(false, false),
+            &mut ExecutionScopes::new(),
+            &hint_data,
+            &HashMap::new(),
+        );
+        assert!(!matches!(result, Err(HintError::UnknownHint(_))), "{code}");
+    }
+}
+
+#[test]
+fn test_builtin_hint_codes_cairo_vm_revision() {
+    let manifest = include_str!("../../../Cargo.toml");
+    let cairo_vm = manifest
+        .lines()
+        .find(|line| line.starts_with("cairo-vm "))
+        .unwrap();
+    assert!(
+        cairo_vm.contains(&format!("rev = \"{CAIRO_VM_REVISION}\"")),
+        "The Cairo VM was updated, check BUILTIN_HINT_CODES against its builtin hint processor \
+         and update CAIRO_VM_REVISION"
+    );
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use cairo_bootloader_hints::bootloader_output::TaskOutput;
+use cairo_bootloader_hints::types::{
+    BootloaderConfig, BootloaderInput, PackedOutput, RunProgramTask, SimpleBootloaderInput,
+    TaskSpec, UserArg,
+};
+use cairo_lang_casm::casm;
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, ExternalHint, Hint};
+use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_casm::operand::{CellRef, Register, ResOperand};
+use cairo_runner::runner::BootloaderRunner;
+use cairo_vm::serde::deserialize_program::{ApTracking, FlowTrackingData, HintParams};
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::types::program::Program;
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::Felt252;
+
+/// Code under which the programs below reference their Cairo 1 hint.
+const TASK_HINT: &str = "task_hint";
+
+fn ap_cell(offset: i16) -> CellRef {
+    CellRef {
+        register: Register::AP,
+        offset,
+    }
+}
+
+/// Builds a Cairo 1 task from CASM instructions taking the output builtin. The hint of an
+/// instruction, if any, is referenced as `TASK_HINT`.
+fn cairo1_task(instructions: Vec<Instruction>, user_args: Vec<Vec<UserArg>>) -> TaskSpec {
+    let mut data = vec![];
+    let mut program_hints = HashMap::new();
+    let mut task_hints = HashMap::new();
+    for instruction in instructions {
+        if let Some(hint) = instruction.hints.first() {
+            let hint_params = HintParams {
+                code: TASK_HINT.to_string(),
+                accessible_scopes: vec![],
+                flow_tracking_data: FlowTrackingData {
+                    ap_tracking: ApTracking::new(),
+                    reference_ids: HashMap::new(),
+                },
+            };
+            program_hints.insert(data.len(), vec![hint_params]);
+            task_hints.insert(TASK_HINT.to_string(), hint.clone());
+        }
+        data.extend(
+            instruction.assemble().encode().iter().map(|word| {
+                MaybeRelocatable::from(Felt252::from_dec_str(&word.to_string()).unwrap())
+            }),
+        );
+    }
+
+    let program = Program::new(
+        vec![BuiltinName::output],
+        data,
+        Some(0),
+        program_hints,
+        Default::default(),
+        Default::default(),
+        vec![],
+        None,
+    )
+    .unwrap();
+    TaskSpec::RunProgram(
+        RunProgramTask::new(program, HashMap::new(), false)
+            .with_hints(task_hints)
+            .with_user_args(user_args),
+    )
+}
+
+/// A task outputting the first element of its argument array, read with a `WriteRunParam` hint.
+fn first_arg_task(args: &[u64]) -> TaskSpec {
+    let mut ctx = casm! {
+        [ap + 0] = 0;
+        [ap + 3] = [[ap + 1] + 0];
+        [ap + 3] = [[fp + -3] + 0];
+        [ap + 4] = [fp + -3] + 1;
+        ap += 5;
+        ret;
+    };
+    // Writes the bounds of the argument array of index `[ap + 0]` to `[ap + 1]` and `[ap + 2]`.
+    ctx.instructions[1].hints = vec![Hint::External(ExternalHint::WriteRunParam {
+        index: ResOperand::Deref(ap_cell(0)),
+        dst: ap_cell(1),
+    })];
+    let user_args = vec![vec![UserArg::Array(
+        args.iter()
+            .copied()
+            .map(Felt252::from)
+            .map(UserArg::Value)
+            .collect(),
+    )]];
+    cairo1_task(ctx.instructions, user_args)
+}
+
+/// A task writing 42 to a segment allocated with an `AllocSegment` hint, and outputting it.
+fn alloc_segment_task() -> TaskSpec {
+    let mut ctx = casm! {
+        [ap + 1] = 42;
+        [ap + 1] = [[ap + 0] + 0];
+        [ap + 1] = [[fp + -3] + 0];
+        [ap + 2] = [fp + -3] + 1;
+        ap += 3;
+        ret;
+    };
+    ctx.instructions[1].hints = vec![Hint::Core(CoreHintBase::Core(CoreHint::AllocSegment {
+        dst: ap_cell(0),
+    }))];
+    cairo1_task(ctx.instructions, vec![])
+}
+
+fn run_tasks(tasks: Vec<TaskSpec>) -> Vec<TaskOutput> {
+    let n_tasks = tasks.len();
+    let bootloader_input = BootloaderInput {
+        simple_bootloader_input: SimpleBootloaderInput {
+            fact_topologies_path: None,
+            single_page: false,
+            tasks,
+        },
+        bootloader_config: BootloaderConfig {
+            simple_bootloader_program_hash: Felt252::ZERO,
+            supported_cairo_verifier_program_hashes: vec![],
+        },
+        packed_outputs: vec![PackedOutput::Plain(vec![]); n_tasks],
+        ignore_fact_topologies: true,
+    };
+    BootloaderRunner::new(bootloader_input)
+        .with_proof_mode(false)
+        .run()
+        .unwrap()
+        .bootloader_output()
+        .unwrap()
+        .tasks
+}
+
+fn plain_output(task: &TaskOutput) -> &[Felt252] {
+    let TaskOutput::Plain { output, .. } = task else {
+        panic!("Expected a plain task output");
+    };
+    output
+}
+
+#[test]
+fn test_cairo1_tasks_read_their_own_args() {
+    let tasks = run_tasks(vec![first_arg_task(&[3, 4]), first_arg_task(&[5])]);
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(plain_output(&tasks[0]), [Felt252::from(3)]);
+    assert_eq!(plain_output(&tasks[1]), [Felt252::from(5)]);
+}
+
+#[test]
+fn test_cairo1_tasks_use_their_own_hints() {
+    // Both programs reference their hint as `TASK_HINT`, each task resolves it from its own
+    // hint table.
+    let tasks = run_tasks(vec![first_arg_task(&[3]), alloc_segment_task()]);
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(plain_output(&tasks[0]), [Felt252::from(3)]);
+    assert_eq!(plain_output(&tasks[1]), [Felt252::from(42)]);
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use cairo_bootloader_hints::codes::{BOOTLOADER_LOAD_BOOTLOADER_CONFIG, EXECUTE_TASK_CALL_TASK};
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, ExternalHint, Hint, StarknetHint};
+use cairo_lang_casm::operand::{CellRef, Register, ResOperand};
+use cairo_runner::hint_coverage::{hint_coverage, HintKind, HintUsage};
+use cairo_runner::hint_processor::BootloaderHintProcessor;
+use cairo_vm::serde::deserialize_program::{ApTracking, FlowTrackingData, HintParams};
+use cairo_vm::types::program::Program;
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::Felt252;
+
+/// Builds a program running `hint_codes` at pc 0.
+fn program_with_hints(hint_codes: &[&str]) -> Program {
+    let hints = hint_codes
+        .iter()
+        .map(|code| HintParams {
+            code: code.to_string(),
+            accessible_scopes: vec![],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids: HashMap::new(),
+            },
+        })
+        .collect();
+    let ret = Felt252::from(0x208b7fff7fff7ffe_u64);
+    Program::new(
+        vec![],
+        vec![MaybeRelocatable::from(ret)],
+        Some(0),
+        HashMap::from([(0, hints)]),
+        Default::default(),
+        Default::default(),
+        vec![],
+        None,
+    )
+    .unwrap()
+}
+
+const ADD_SEGMENT: &str = "memory[ap] = segments.add()";
+const UNKNOWN_HINT: &str = "ids.x = unknown_function()";
+
+fn usage(code: &str, kind: HintKind, count: usize) -> HintUsage {
+    HintUsage {
+        code: code.to_string(),
+        kind,
+        count,
+    }
+}
+
+fn ap_cell() -> CellRef {
+    CellRef {
+        register: Register::AP,
+        offset: 0,
+    }
+}
+
+#[test]
+fn test_hint_coverage_cairo0() {
+    let program = program_with_hints(&[
+        UNKNOWN_HINT,
+        ADD_SEGMENT,
+        EXECUTE_TASK_CALL_TASK,
+        BOOTLOADER_LOAD_BOOTLOADER_CONFIG,
+        ADD_SEGMENT,
+    ]);
+
+    let coverage = hint_coverage(&BootloaderHintProcessor::new(), &program, &HashMap::new());
+    assert_eq!(
+        coverage.hints,
+        vec![
+            usage(BOOTLOADER_LOAD_BOOTLOADER_CONFIG, HintKind::Bootloader, 1),
+            usage(EXECUTE_TASK_CALL_TASK, HintKind::Extensive, 1),
+            usage(ADD_SEGMENT, HintKind::CairoVm, 2),
+            usage(UNKNOWN_HINT, HintKind::Unknown, 1),
+        ]
+    );
+    assert!(!coverage.is_complete());
+    assert_eq!(coverage.count_by_kind()[&HintKind::CairoVm], 1);
+}
+
+#[test]
+fn test_hint_coverage_cairo1() {
+    let core_hint = "AllocSegment";
+    let external_hint = "AddMarker";
+    let syscall_hint = "SystemCall";
+    let program = program_with_hints(&[core_hint, external_hint, syscall_hint]);
+    let cairo1_hints = HashMap::from([
+        (
+            core_hint.to_string(),
+            Hint::Core(CoreHintBase::Core(CoreHint::AllocSegment {
+                dst: ap_cell(),
+            })),
+        ),
+        (
+            external_hint.to_string(),
+            Hint::External(ExternalHint::AddMarker {
+                start: ResOperand::Deref(ap_cell()),
+                end: ResOperand::Deref(ap_cell()),
+            }),
+        ),
+        (
+            syscall_hint.to_string(),
+            Hint::Starknet(StarknetHint::SystemCall {
+                system: ResOperand::Deref(ap_cell()),
+            }),
+        ),
+    ]);
+
+    let coverage = hint_coverage(&BootloaderHintProcessor::new(), &program, &cairo1_hints);
+    assert_eq!(
+        coverage.hints,
+        vec![
+            usage(core_hint, HintKind::Cairo1Core, 1),
+            usage(external_hint, HintKind::Cairo1External, 1),
+            usage(syscall_hint, HintKind::Unknown, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_hint_coverage_added_hint() {
+    let mut hint_processor = BootloaderHintProcessor::new();
+    hint_processor.add_hint(UNKNOWN_HINT.to_string(), |_, _, _, _| Ok(()));
+
+    let program = program_with_hints(&[UNKNOWN_HINT]);
+    let coverage = hint_coverage(&hint_processor, &program, &HashMap::new());
+    assert_eq!(
+        coverage.hints,
+        vec![usage(UNKNOWN_HINT, HintKind::Bootloader, 1)]
+    );
+    assert!(coverage.is_complete());
+}
+
+#[test]
+fn test_unsupported_hints() {
+    let program = program_with_hints(&[UNKNOWN_HINT, ADD_SEGMENT, UNKNOWN_HINT]);
+
+    let unsupported_hints =
+        BootloaderHintProcessor::new().unsupported_hints(&program, &HashMap::new());
+    assert_eq!(unsupported_hints, vec![UNKNOWN_HINT.to_string()]);
+}
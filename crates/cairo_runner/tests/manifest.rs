@@ -0,0 +1,208 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use cairo_bootloader_hints::types::PackedOutput;
+use cairo_runner::manifest::{Manifest, ManifestError, MANIFEST_VERSION};
+use cairo_runner::recursion::VerifiedRun;
+use cairo_runner::task::TaskSource;
+use cairo_vm::Felt252;
+
+use common::example_path;
+
+fn manifests_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/manifests")
+}
+
+/// The tasks of `tests/manifests/tasks.{json,toml}`, resolved against `base_dir`.
+fn expected_tasks(base_dir: &Path) -> Vec<TaskSource> {
+    vec![
+        TaskSource::Cairo0 {
+            path: base_dir.join("../../../../examples/fibonacci_with_hint.json"),
+            program_input: Some(base_dir.join("../../../../examples/fibonacci_input.json")),
+            use_poseidon: false,
+        },
+        TaskSource::CairoPie {
+            path: base_dir.join("../../../../examples/noop.zip"),
+            use_poseidon: true,
+        },
+    ]
+}
+
+fn noop_task() -> TaskSource {
+    TaskSource::CairoPie {
+        path: example_path("noop.zip"),
+        use_poseidon: false,
+    }
+}
+
+fn verified_run(task: usize) -> VerifiedRun {
+    VerifiedRun {
+        task,
+        program_output: PathBuf::from("output.json"),
+        fact_topologies: None,
+    }
+}
+
+#[test]
+fn test_load_json_manifest() {
+    let manifest = Manifest::from_file(&manifests_dir().join("tasks.json")).unwrap();
+
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+    assert_eq!(manifest.tasks, expected_tasks(&manifests_dir()));
+    assert_eq!(
+        manifest.bootloader_config.simple_bootloader_program_hash,
+        Felt252::ZERO
+    );
+    assert_eq!(manifest.packed_outputs, None);
+    assert!(!manifest.single_page);
+    assert!(!manifest.ignore_fact_topologies);
+    assert!(manifest.verified_runs.is_empty());
+}
+
+#[test]
+fn test_load_toml_manifest() {
+    let manifest = Manifest::from_file(&manifests_dir().join("tasks.toml")).unwrap();
+
+    assert_eq!(manifest.tasks, expected_tasks(&manifests_dir()));
+    assert_eq!(
+        manifest.bootloader_config.simple_bootloader_program_hash,
+        Felt252::ONE
+    );
+    assert_eq!(
+        manifest
+            .bootloader_config
+            .supported_cairo_verifier_program_hashes,
+        vec![Felt252::from(2)]
+    );
+    assert!(manifest.ignore_fact_topologies);
+}
+
+#[test]
+fn test_resolve_paths() {
+    let manifest = Manifest::from_file(&manifests_dir().join("tasks.toml")).unwrap();
+
+    assert_eq!(
+        manifest.fact_topologies_path,
+        Some(manifests_dir().join("fact_topologies.json"))
+    );
+    assert_eq!(
+        manifest.verified_runs,
+        vec![VerifiedRun {
+            task: 1,
+            program_output: manifests_dir().join("inner/output.json"),
+            fact_topologies: None,
+        }]
+    );
+}
+
+#[test]
+fn test_reject_unsupported_version() {
+    // The version is checked before the content, which may have fields unknown to this version.
+    let result = Manifest::from_file(&manifests_dir().join("unsupported_version.json"));
+
+    assert!(matches!(
+        result,
+        Err(ManifestError::UnsupportedVersion {
+            found: 2,
+            expected: MANIFEST_VERSION
+        })
+    ));
+}
+
+#[test]
+fn test_missing_manifest() {
+    let result = Manifest::from_file(&manifests_dir().join("missing.json"));
+    assert!(matches!(result, Err(ManifestError::Io(_, _))));
+}
+
+#[test]
+fn test_validate_no_tasks() {
+    let manifest = Manifest::from_tasks(vec![]);
+    assert!(matches!(manifest.validate(), Err(ManifestError::NoTasks)));
+}
+
+#[test]
+fn test_validate_packed_outputs_length() {
+    let mut manifest = Manifest::from_tasks(vec![noop_task()]);
+    manifest.packed_outputs = Some(vec![PackedOutput::Plain(vec![]); 2]);
+
+    assert!(matches!(
+        manifest.validate(),
+        Err(ManifestError::PackedOutputsLength {
+            n_packed_outputs: 2,
+            n_tasks: 1
+        })
+    ));
+}
+
+#[test]
+fn test_validate_file_not_found() {
+    let manifest = Manifest::from_tasks(vec![
+        noop_task(),
+        TaskSource::Cairo0 {
+            path: example_path("fibonacci_with_hint.json"),
+            program_input: Some(example_path("missing.json")),
+            use_poseidon: false,
+        },
+    ]);
+
+    assert!(matches!(
+        manifest.validate(),
+        Err(ManifestError::FileNotFound { task_index: 1, path }) if path == example_path("missing.json")
+    ));
+}
+
+#[test]
+fn test_validate_conflicting_packed_outputs() {
+    let mut manifest = Manifest::from_tasks(vec![noop_task()]);
+    manifest.packed_outputs = Some(vec![PackedOutput::Plain(vec![])]);
+    manifest.verified_runs = vec![verified_run(0)];
+
+    assert!(matches!(
+        manifest.validate(),
+        Err(ManifestError::ConflictingPackedOutputs)
+    ));
+}
+
+#[test]
+fn test_validate_no_supported_verifiers() {
+    let mut manifest = Manifest::from_tasks(vec![noop_task()]);
+    manifest.verified_runs = vec![verified_run(0)];
+
+    assert!(matches!(
+        manifest.validate(),
+        Err(ManifestError::NoSupportedVerifiers)
+    ));
+}
+
+#[test]
+fn test_validate_verified_run_task_out_of_bounds() {
+    let mut manifest = Manifest::from_tasks(vec![noop_task()]);
+    manifest
+        .bootloader_config
+        .supported_cairo_verifier_program_hashes = vec![Felt252::ONE];
+    manifest.verified_runs = vec![verified_run(1)];
+
+    assert!(matches!(
+        manifest.validate(),
+        Err(ManifestError::VerifiedRunTaskOutOfBounds {
+            task_index: 1,
+            n_tasks: 1
+        })
+    ));
+}
+
+#[test]
+fn test_validate_duplicate_verified_run() {
+    let mut manifest = Manifest::from_tasks(vec![noop_task()]);
+    manifest
+        .bootloader_config
+        .supported_cairo_verifier_program_hashes = vec![Felt252::ONE];
+    manifest.verified_runs = vec![verified_run(0), verified_run(0)];
+
+    assert!(matches!(
+        manifest.validate(),
+        Err(ManifestError::DuplicateVerifiedRun(0))
+    ));
+}
@@ -0,0 +1,29 @@
+use cairo_runner::hint_processor::{PanicTraceback, TracebackFrame};
+use cairo_vm::types::relocatable::Relocatable;
+
+fn frame(pc: Relocatable, task_pc: Option<usize>) -> TracebackFrame {
+    TracebackFrame { pc, task_pc }
+}
+
+fn traceback() -> PanicTraceback {
+    PanicTraceback {
+        task_id: Some(1),
+        frames: vec![
+            frame(Relocatable::from((0, 120)), None),
+            frame(Relocatable::from((5, 12)), Some(10)),
+            frame(Relocatable::from((5, 42)), Some(40)),
+        ],
+    }
+}
+
+#[test]
+fn test_display_traceback() {
+    assert_eq!(
+        traceback().to_string(),
+        "Task 1 panicked\n\
+         Traceback (most recent call last):\n    \
+         <bootloader> (pc 0:120)\n    \
+         <task> (pc 10)\n    \
+         <task> (pc 40)\n"
+    );
+}
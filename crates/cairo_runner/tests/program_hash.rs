@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use cairo_runner::manifest::Manifest;
+use cairo_runner::program_hash::{program_hash_from_file, ProgramHashFunction, ProgramKind};
+use cairo_runner::runner::BootloaderRunner;
+use cairo_runner::task::TaskSource;
+use rstest::rstest;
+
+fn root_path(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(path)
+}
+
+const CAIRO0_PROGRAM: &str = "examples/fibonacci_with_hint.json";
+const CAIRO_PIE: &str = "examples/noop.zip";
+const EXECUTABLE: &str = "crates/cairo_runner/tests/programs/return_output.executable.json";
+
+#[rstest]
+// Expected hashes of cairo-lang's `compute_program_hash_chain`, computed with starknet-crypto over
+// the same data chains. `make check-program-hashes`, run in CI, checks them against cairo-lang,
+// and `make program-hashes` prints the cairo-lang ones to refresh them.
+#[case::cairo0_pedersen(
+    CAIRO0_PROGRAM,
+    ProgramKind::Cairo0,
+    ProgramHashFunction::Pedersen,
+    "0x16701409cc8ea500943fe09fdc007d3f37035f78f7446073e4f4b9f5b17282f"
+)]
+#[case::cairo0_poseidon(
+    CAIRO0_PROGRAM,
+    ProgramKind::Cairo0,
+    ProgramHashFunction::Poseidon,
+    "0x7a496beac59cc4d79f9f29a38142539267f8227c57d6ec229bef352294bd8d9"
+)]
+#[case::cairo_pie_pedersen(
+    CAIRO_PIE,
+    ProgramKind::CairoPie,
+    ProgramHashFunction::Pedersen,
+    "0xe6cc5fc947a7c9e49fdb885d806bc4bca87c46687b3a470e254b8b5e8ddb58"
+)]
+#[case::cairo_pie_poseidon(
+    CAIRO_PIE,
+    ProgramKind::CairoPie,
+    ProgramHashFunction::Poseidon,
+    "0x5fedd7544162b29b171731284c67eb36e439185d99c83614b9ad8d5333b9a08"
+)]
+#[case::executable_pedersen(
+    EXECUTABLE,
+    ProgramKind::Executable,
+    ProgramHashFunction::Pedersen,
+    "0x455e6aa39008aa1ee4536b4612a56a94b5e2656cc7c4a6673bf93656d54afdd"
+)]
+#[case::executable_poseidon(
+    EXECUTABLE,
+    ProgramKind::Executable,
+    ProgramHashFunction::Poseidon,
+    "0x1ec9c8d124e9f466630b306f6e1e715d7cfd3765ede479f15dc41c32761dc4"
+)]
+fn test_program_hash_from_file(
+    #[case] path: &str,
+    #[case] kind: ProgramKind,
+    #[case] hash_function: ProgramHashFunction,
+    #[case] expected_hash: &str,
+) {
+    let program_hash = program_hash_from_file(&root_path(path), Some(kind), hash_function).unwrap();
+    assert_eq!(format!("{program_hash:#x}"), expected_hash);
+
+    // The kind is detected from the content of the file.
+    let detected_hash = program_hash_from_file(&root_path(path), None, hash_function).unwrap();
+    assert_eq!(detected_hash, program_hash);
+}
+
+#[rstest]
+// Expected hashes printed by `make blake2s-program-hashes`.
+#[case::cairo0(
+    CAIRO0_PROGRAM,
+    "0x848d5ff0184189d50a9ce1f4cc0d9e01ef14e184016368268701e1cc"
+)]
+#[case::cairo_pie(
+    CAIRO_PIE,
+    "0xd896bd82dcd3157cd044876bdaa4a68e0c2518c13f44304fbdb42e6a"
+)]
+#[case::executable(
+    EXECUTABLE,
+    "0xa82c891110008664481e3e972637f3b07ec0ab141b064c580c78dbb"
+)]
+fn test_program_hash_from_file_blake2s(#[case] path: &str, #[case] expected_hash: &str) {
+    let program_hash =
+        program_hash_from_file(&root_path(path), None, ProgramHashFunction::Blake2s).unwrap();
+    assert_eq!(format!("{program_hash:#x}"), expected_hash);
+}
+
+#[rstest]
+#[case::cairo0(CAIRO0_PROGRAM, Some("examples/fibonacci_input.json"))]
+#[case::cairo_pie(CAIRO_PIE, None)]
+#[case::executable(EXECUTABLE, None)]
+fn test_program_hash_from_file_matches_bootloader(
+    #[case] path: &str,
+    #[case] input_file: Option<&str>,
+) {
+    // The bootloader hashes the task program with Blake2s in Cairo and outputs the hash.
+    let manifest = Manifest::from_tasks(vec![TaskSource::Auto {
+        path: root_path(path),
+        input_file: input_file.map(root_path),
+        entrypoint: None,
+        use_poseidon: false,
+    }]);
+    let tasks = BootloaderRunner::from_manifest(&manifest)
+        .unwrap()
+        .with_proof_mode(false)
+        .run()
+        .unwrap()
+        .bootloader_output()
+        .unwrap()
+        .tasks;
+
+    let program_hash =
+        program_hash_from_file(&root_path(path), None, ProgramHashFunction::Blake2s).unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(*tasks[0].program_hash(), program_hash);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use std::collections::HashMap;
+
+use cairo_bootloader_hints::bootloader_output::TaskOutput;
+use cairo_runner::manifest::Manifest;
+use cairo_runner::runner::BootloaderRunner;
+use cairo_runner::task::TaskSource;
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData;
+use cairo_vm::hint_processor::builtin_hint_processor::hint_utils::insert_value_from_var_name;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
+
+use common::example_path;
+
+const FIBONACCI_HINT: &str = "ids.fibonacci_claim_index = program_input['fibonacci_claim_index']";
+
+/// Implements the hint of `examples/fibonacci_with_hint.json`.
+fn fibonacci_claim_index(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    hint_data: &HintProcessorData,
+    _constants: &HashMap<String, Felt252>,
+) -> Result<(), HintError> {
+    let program_input: &HashMap<String, serde_json::Value> =
+        exec_scopes.get_ref("program_input")?;
+    let claim_index = program_input
+        .get("fibonacci_claim_index")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| HintError::CustomHint("Missing fibonacci_claim_index".into()))?;
+
+    insert_value_from_var_name(
+        "fibonacci_claim_index",
+        Felt252::from(claim_index),
+        vm,
+        &hint_data.ids_data,
+        &hint_data.ap_tracking,
+    )
+}
+
+#[test]
+fn test_cairo0_task_reads_program_input() {
+    let manifest = Manifest::from_tasks(vec![TaskSource::Cairo0 {
+        path: example_path("fibonacci_with_hint.json"),
+        program_input: Some(example_path("fibonacci_input.json")),
+        use_poseidon: false,
+    }]);
+
+    let result = BootloaderRunner::from_manifest(&manifest)
+        .unwrap()
+        .with_cairo0_hint(FIBONACCI_HINT, fibonacci_claim_index)
+        .with_proof_mode(false)
+        .run()
+        .unwrap();
+
+    let tasks = result.bootloader_output().unwrap().tasks;
+    assert_eq!(tasks.len(), 1);
+    let TaskOutput::Plain { output, .. } = &tasks[0] else {
+        panic!("Expected a plain task output");
+    };
+    // The program outputs the claim index and the corresponding Fibonacci number.
+    assert_eq!(output, &vec![Felt252::from(10), Felt252::from(144)]);
+}
+
+#[test]
+fn test_cairo0_task_without_program_input_fails() {
+    let manifest = Manifest::from_tasks(vec![TaskSource::Cairo0 {
+        path: example_path("fibonacci_with_hint.json"),
+        program_input: None,
+        use_poseidon: false,
+    }]);
+
+    let result = BootloaderRunner::from_manifest(&manifest)
+        .unwrap()
+        .with_cairo0_hint(FIBONACCI_HINT, fibonacci_claim_index)
+        .with_proof_mode(false)
+        .run();
+
+    assert!(result.is_err());
+}
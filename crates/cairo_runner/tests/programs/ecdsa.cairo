@@ -0,0 +1,24 @@
+%builtins output ecdsa
+
+from starkware.cairo.common.cairo_builtins import SignatureBuiltin
+from starkware.cairo.common.serialize import serialize_word
+from starkware.cairo.common.signature import verify_ecdsa_signature
+
+// A message signed with the private key
+// 0x3c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc.
+const MESSAGE = 0x5bb9440e27889a364bcb678b1f679ecd1347acdedcbf36e83494f857cc58026;
+const PUBLIC_KEY = 0x77a3b314db07c45076d11f62b6f9e748a39790441823307743cf00d6597ea43;
+const SIGNATURE_R = 0x6a64a0eecebd63ab4587b37572c2137ff453d17c57a4362f03812c8d3f780b5;
+const SIGNATURE_S = 0x270eb27c368b04ac981b14aca6c5ac84b94683c59218bf08fcddce32f447a7b;
+
+// Outputs the message after checking its signature with the ECDSA builtin.
+func main{output_ptr: felt*, ecdsa_ptr: SignatureBuiltin*}() {
+    verify_ecdsa_signature(
+        message=MESSAGE,
+        public_key=PUBLIC_KEY,
+        signature_r=SIGNATURE_R,
+        signature_s=SIGNATURE_S,
+    );
+    serialize_word(MESSAGE);
+    return ();
+}
@@ -0,0 +1,41 @@
+%builtins output range_check96 add_mod mul_mod
+
+from starkware.cairo.common.alloc import alloc
+from starkware.cairo.common.cairo_builtins import ModBuiltin, UInt384
+from starkware.cairo.common.modulo import run_mod_p_circuit
+from starkware.cairo.common.serialize import serialize_word
+
+// Outputs (30 + 80) * 80 mod 97 = 70, computed with the add_mod and mul_mod builtins.
+func main{
+    output_ptr: felt*, range_check96_ptr: felt*, add_mod_ptr: ModBuiltin*, mul_mod_ptr: ModBuiltin*
+}() {
+    alloc_locals;
+
+    let (local values: UInt384*) = alloc();
+    assert values[0] = UInt384(d0=30, d1=0, d2=0, d3=0);
+    assert values[1] = UInt384(d0=80, d1=0, d2=0, d3=0);
+
+    // values[2] = values[0] + values[1], with offsets in felts.
+    let (local add_mod_offsets: felt*) = alloc();
+    assert add_mod_offsets[0] = 0;
+    assert add_mod_offsets[1] = 4;
+    assert add_mod_offsets[2] = 8;
+
+    // values[3] = values[2] * values[1].
+    let (local mul_mod_offsets: felt*) = alloc();
+    assert mul_mod_offsets[0] = 8;
+    assert mul_mod_offsets[1] = 4;
+    assert mul_mod_offsets[2] = 12;
+
+    run_mod_p_circuit(
+        p=UInt384(d0=97, d1=0, d2=0, d3=0),
+        values_ptr=values,
+        add_mod_offsets_ptr=add_mod_offsets,
+        add_mod_n=1,
+        mul_mod_offsets_ptr=mul_mod_offsets,
+        mul_mod_n=1,
+    );
+
+    serialize_word(values[3].d0);
+    return ();
+}
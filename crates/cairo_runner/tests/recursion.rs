@@ -0,0 +1,408 @@
+mod common;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use cairo_bootloader_hints::bootloader_output::TaskOutput;
+use cairo_bootloader_hints::fact_topologies::FactTopology;
+use cairo_bootloader_hints::facts::{
+    compute_bootloader_facts, single_page_fact_topology, FactHashFunction,
+};
+use cairo_bootloader_hints::types::{
+    BootloaderConfig, BootloaderInput, CairoPiePath, CompositePackedOutput, PackedOutput,
+    RunProgramTask, SimpleBootloaderInput, TaskSpec,
+};
+use cairo_lang_casm::casm;
+use cairo_runner::recursion::{composite_packed_output, RecursionError, VerifiedRun};
+use cairo_runner::runner::{BootloaderRunResult, BootloaderRunner};
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData;
+use cairo_vm::serde::deserialize_program::{ApTracking, FlowTrackingData, HintParams};
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::types::program::Program;
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
+
+use common::example_path;
+
+/// A directory of its own for each test, as tests run concurrently.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "cairo-runner-recursion-{}-{name}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn felts(values: &[u64]) -> Vec<Felt252> {
+    values.iter().copied().map(Felt252::from).collect()
+}
+
+fn fact_topology(tree_structure: Vec<usize>, page_sizes: Vec<usize>) -> FactTopology {
+    FactTopology {
+        tree_structure,
+        page_sizes,
+    }
+}
+
+/// Output of a simple bootloader run with two tasks, whose outputs are `[7, 8, 9]` and `[10]`.
+fn verified_run_output() -> Vec<Felt252> {
+    felts(&[2, 5, 0x10, 7, 8, 9, 3, 0x20, 10])
+}
+
+#[test]
+fn test_verified_run_load() {
+    let dir = test_dir("load");
+    let program_output = dir.join("output.json");
+    let fact_topologies = dir.join("fact_topologies.json");
+    std::fs::write(
+        &program_output,
+        serde_json::to_string(&verified_run_output()).unwrap(),
+    )
+    .unwrap();
+    std::fs::write(
+        &fact_topologies,
+        r#"{"fact_topologies": [
+            {"tree_structure": [2, 2], "page_sizes": [1, 2]},
+            {"tree_structure": [1, 0], "page_sizes": [1]}
+        ]}"#,
+    )
+    .unwrap();
+
+    let verified_run = VerifiedRun {
+        task: 0,
+        program_output,
+        fact_topologies: Some(fact_topologies),
+    };
+    let packed_output = verified_run.load().unwrap();
+
+    assert_eq!(
+        packed_output,
+        CompositePackedOutput {
+            outputs: verified_run_output(),
+            subtasks: vec![PackedOutput::Plain(vec![]); 2],
+            fact_topologies: vec![
+                fact_topology(vec![2, 2], vec![1, 2]),
+                single_page_fact_topology(1),
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_verified_run_load_without_fact_topologies() {
+    let dir = test_dir("load_without_fact_topologies");
+    let program_output = dir.join("output.json");
+    std::fs::write(
+        &program_output,
+        serde_json::to_string(&verified_run_output()).unwrap(),
+    )
+    .unwrap();
+
+    let verified_run = VerifiedRun {
+        task: 0,
+        program_output,
+        fact_topologies: None,
+    };
+
+    assert_eq!(
+        verified_run.load().unwrap().fact_topologies,
+        vec![single_page_fact_topology(3), single_page_fact_topology(1)]
+    );
+}
+
+#[test]
+fn test_verified_run_load_missing_output() {
+    let verified_run = VerifiedRun {
+        task: 0,
+        program_output: test_dir("load_missing_output").join("missing.json"),
+        fact_topologies: None,
+    };
+
+    assert!(matches!(verified_run.load(), Err(RecursionError::Io(_, _))));
+}
+
+#[test]
+fn test_composite_packed_output_wrong_number_of_subtasks() {
+    let result = composite_packed_output(
+        verified_run_output(),
+        Some(vec![PackedOutput::Plain(vec![])]),
+        None,
+    );
+
+    assert!(matches!(
+        result,
+        Err(RecursionError::WrongNumberOfSubtasks {
+            n_tasks: 2,
+            n_subtasks: 1
+        })
+    ));
+}
+
+#[test]
+fn test_composite_packed_output_wrong_number_of_fact_topologies() {
+    let result = composite_packed_output(
+        verified_run_output(),
+        None,
+        Some(vec![single_page_fact_topology(3)]),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RecursionError::WrongNumberOfFactTopologies {
+            n_tasks: 2,
+            n_fact_topologies: 1
+        })
+    ));
+}
+
+#[test]
+fn test_composite_packed_output_size_mismatch() {
+    let result = composite_packed_output(
+        verified_run_output(),
+        None,
+        Some(vec![
+            single_page_fact_topology(3),
+            single_page_fact_topology(2),
+        ]),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RecursionError::OutputSizeMismatch {
+            task_index: 1,
+            expected: 2,
+            actual: 1
+        })
+    ));
+}
+
+#[test]
+fn test_composite_packed_output_nested() {
+    // The second task of the verified run is itself a Cairo verifier, whose output holds the
+    // hashes of the simple bootloader and of the output of the run it verified.
+    let nested = composite_packed_output(verified_run_output(), None, None).unwrap();
+    let output = felts(&[2, 3, 0x30, 11, 4, 0x40, 0x50, 0x60]);
+    let subtasks = vec![
+        PackedOutput::Plain(vec![]),
+        PackedOutput::Composite(nested.clone()),
+    ];
+    let fact_topologies = vec![
+        single_page_fact_topology(1),
+        // Replaced by the fact topologies of the nested run, so not checked against the output.
+        fact_topology(vec![1, 0], vec![10]),
+    ];
+
+    let packed_output = composite_packed_output(
+        output.clone(),
+        Some(subtasks.clone()),
+        Some(fact_topologies),
+    )
+    .unwrap();
+
+    assert_eq!(packed_output.outputs, output);
+    assert_eq!(packed_output.subtasks, subtasks);
+    let plain_fact_topologies: Vec<FactTopology> = packed_output
+        .get_plain_fact_topologies()
+        .unwrap()
+        .into_iter()
+        .cloned()
+        .collect();
+    assert_eq!(
+        plain_fact_topologies,
+        vec![
+            single_page_fact_topology(1),
+            single_page_fact_topology(3),
+            single_page_fact_topology(1),
+        ]
+    );
+}
+
+const VERIFIER_OUTPUT_HINT: &str =
+    "memory[ap] = program_input['program_hash']; memory[ap + 1] = program_input['output_hash']";
+
+/// Implements `VERIFIER_OUTPUT_HINT`, writing the output of the stand-in verifier from its
+/// program input.
+fn verifier_output(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    _hint_data: &HintProcessorData,
+    _constants: &HashMap<String, Felt252>,
+) -> Result<(), HintError> {
+    let program_input: &HashMap<String, serde_json::Value> =
+        exec_scopes.get_ref("program_input")?;
+    for (offset, key) in ["program_hash", "output_hash"].into_iter().enumerate() {
+        let value = program_input
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .and_then(|value| Felt252::from_hex(value).ok())
+            .ok_or_else(|| HintError::CustomHint(format!("Missing {key}").into_boxed_str()))?;
+        vm.insert_value((vm.get_ap() + offset)?, value)?;
+    }
+    Ok(())
+}
+
+/// A program standing in for a Cairo verifier: it outputs the hash of the verified program and
+/// the hash of its output, as given in its program input.
+fn stand_in_verifier() -> Program {
+    let ctx = casm! {
+        [ap + 0] = [[fp + -3] + 0];
+        [ap + 1] = [[fp + -3] + 1];
+        [ap + 2] = [fp + -3] + 2;
+        ap += 3;
+        ret;
+    };
+    let data = ctx
+        .instructions
+        .iter()
+        .flat_map(|instruction| instruction.assemble().encode())
+        .map(|word| MaybeRelocatable::from(Felt252::from_dec_str(&word.to_string()).unwrap()))
+        .collect();
+    let hint = HintParams {
+        code: VERIFIER_OUTPUT_HINT.to_string(),
+        accessible_scopes: vec![],
+        flow_tracking_data: FlowTrackingData {
+            ap_tracking: ApTracking::new(),
+            reference_ids: HashMap::new(),
+        },
+    };
+    Program::new(
+        vec![BuiltinName::output],
+        data,
+        Some(0),
+        HashMap::from([(0, vec![hint])]),
+        Default::default(),
+        Default::default(),
+        vec![],
+        None,
+    )
+    .unwrap()
+}
+
+fn verifier_task(program_hash: Felt252, output_hash: Felt252) -> TaskSpec {
+    let program_input = HashMap::from([
+        (
+            "program_hash".to_string(),
+            serde_json::Value::from(format!("{program_hash:#x}")),
+        ),
+        (
+            "output_hash".to_string(),
+            serde_json::Value::from(format!("{output_hash:#x}")),
+        ),
+    ]);
+    TaskSpec::RunProgram(RunProgramTask::new(
+        stand_in_verifier(),
+        program_input,
+        false,
+    ))
+}
+
+fn run(
+    tasks: Vec<TaskSpec>,
+    bootloader_config: BootloaderConfig,
+    packed_outputs: Vec<PackedOutput>,
+) -> BootloaderRunResult {
+    let bootloader_input = BootloaderInput {
+        simple_bootloader_input: SimpleBootloaderInput {
+            fact_topologies_path: None,
+            single_page: false,
+            tasks,
+        },
+        bootloader_config,
+        packed_outputs,
+        ignore_fact_topologies: true,
+    };
+    BootloaderRunner::new(bootloader_input)
+        .with_cairo0_hint(VERIFIER_OUTPUT_HINT, verifier_output)
+        .with_proof_mode(false)
+        .run()
+        .unwrap()
+}
+
+fn no_verifiers() -> BootloaderConfig {
+    BootloaderConfig {
+        simple_bootloader_program_hash: Felt252::ZERO,
+        supported_cairo_verifier_program_hashes: vec![],
+    }
+}
+
+#[test]
+fn test_verifier_task_unpacks_previous_run() {
+    let dir = test_dir("verifier_task");
+
+    // The previous run, whose output the verifier task consumes.
+    let inner_tasks = vec![
+        TaskSpec::CairoPiePath(CairoPiePath {
+            path: example_path("noop.zip"),
+            use_poseidon: false,
+        }),
+        TaskSpec::CairoPiePath(CairoPiePath {
+            path: example_path("fibonacci_with_output.zip"),
+            use_poseidon: false,
+        }),
+    ];
+    let inner_result = run(
+        inner_tasks,
+        no_verifiers(),
+        vec![PackedOutput::Plain(vec![]); 2],
+    );
+    let inner_output = inner_result.output.clone();
+    let program_output = dir.join("output.json");
+    std::fs::write(
+        &program_output,
+        serde_json::to_string(&inner_output).unwrap(),
+    )
+    .unwrap();
+
+    // The hash of the output of the previous run, as found in the output of a Cairo verifier,
+    // is the Poseidon hash of the whole output.
+    let output_root = compute_bootloader_facts(
+        &Felt252::ZERO,
+        &inner_output,
+        None,
+        FactHashFunction::Poseidon,
+    )
+    .unwrap()
+    .bootloader
+    .output_root;
+    let output_hash = Felt252::from_bytes_be(&output_root.0);
+    let simple_bootloader_program_hash = Felt252::from(0x5b1);
+
+    // The bootloader checks that the verifier task is a supported verifier, through the hash it
+    // computes for its program.
+    let verifier_program_hash = {
+        let result = run(
+            vec![verifier_task(simple_bootloader_program_hash, output_hash)],
+            no_verifiers(),
+            vec![PackedOutput::Plain(vec![])],
+        );
+        let tasks = result.bootloader_output().unwrap().tasks;
+        let TaskOutput::Plain { program_hash, .. } = &tasks[0] else {
+            panic!("Expected a plain task output");
+        };
+        *program_hash
+    };
+
+    let verified_run = VerifiedRun {
+        task: 0,
+        program_output,
+        fact_topologies: None,
+    };
+    let outer_result = run(
+        vec![verifier_task(simple_bootloader_program_hash, output_hash)],
+        BootloaderConfig {
+            simple_bootloader_program_hash,
+            supported_cairo_verifier_program_hashes: vec![verifier_program_hash],
+        },
+        vec![PackedOutput::Composite(verified_run.load().unwrap())],
+    );
+
+    // The output of the verifier is replaced by the outputs of the tasks of the previous run.
+    assert_eq!(
+        outer_result.bootloader_output().unwrap(),
+        inner_result.bootloader_output().unwrap()
+    );
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use std::path::PathBuf;
+
+use cairo_bootloader_hints::bootloader_output::TaskOutput;
+use cairo_runner::bootloaders::load_bootloader;
+use cairo_runner::manifest::Manifest;
+use cairo_runner::runner::{parse_layout, BootloaderRunError, BootloaderRunner, RunOptions};
+use cairo_runner::task::TaskSource;
+use cairo_vm::types::layout_name::LayoutName;
+use cairo_vm::Felt252;
+use rstest::rstest;
+
+use common::example_path;
+
+fn dynamic_layout_params_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/layouts/dynamic_params.json")
+}
+
+/// Path of a program compiled, or run to a Cairo PIE, by `make test-programs`.
+fn test_program_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/programs")
+        .join(name)
+}
+
+/// Runs a single task through the bootloader and returns its output.
+fn run_task(path: PathBuf) -> Vec<Felt252> {
+    let manifest = Manifest::from_tasks(vec![TaskSource::Auto {
+        path,
+        input_file: None,
+        entrypoint: None,
+        use_poseidon: false,
+    }]);
+    let mut tasks = BootloaderRunner::from_manifest(&manifest)
+        .unwrap()
+        .with_proof_mode(false)
+        .run()
+        .unwrap()
+        .bootloader_output()
+        .unwrap()
+        .tasks;
+
+    assert_eq!(tasks.len(), 1);
+    let TaskOutput::Plain { output, .. } = tasks.remove(0) else {
+        panic!("Expected a plain task output");
+    };
+    output
+}
+
+#[test]
+fn test_parse_layout() {
+    assert_eq!(
+        parse_layout("starknet_with_keccak").unwrap(),
+        LayoutName::starknet_with_keccak
+    );
+    assert_eq!(parse_layout("dynamic").unwrap(), LayoutName::dynamic);
+}
+
+#[test]
+fn test_parse_unknown_layout() {
+    assert!(matches!(
+        parse_layout("all_stwo"),
+        Err(BootloaderRunError::UnknownLayout(name)) if name == "all_stwo"
+    ));
+}
+
+#[test]
+fn test_layout_without_bootloader_builtins() {
+    // The plain layout has no builtin, while the bootloader uses the output builtin at least.
+    let run_options = RunOptions {
+        layout: LayoutName::plain,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        run_options.validate(&load_bootloader().unwrap()),
+        Err(BootloaderRunError::UnsupportedLayout("plain", _))
+    ));
+}
+
+#[test]
+fn test_dynamic_layout_requires_params() {
+    let run_options = RunOptions {
+        layout: LayoutName::dynamic,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        run_options.validate(&load_bootloader().unwrap()),
+        Err(BootloaderRunError::MissingDynamicLayoutParams)
+    ));
+}
+
+#[test]
+fn test_dynamic_layout_params() {
+    let run_options = RunOptions {
+        layout: LayoutName::dynamic,
+        ..Default::default()
+    }
+    .with_dynamic_layout_params_file(&dynamic_layout_params_path())
+    .unwrap();
+
+    run_options.validate(&load_bootloader().unwrap()).unwrap();
+}
+
+#[test]
+fn test_layout_params_require_dynamic_layout() {
+    let run_options = RunOptions::default()
+        .with_dynamic_layout_params_file(&dynamic_layout_params_path())
+        .unwrap();
+
+    assert!(matches!(
+        run_options.validate(&load_bootloader().unwrap()),
+        Err(BootloaderRunError::UnexpectedDynamicLayoutParams(
+            "all_cairo_stwo"
+        ))
+    ));
+}
+
+#[test]
+fn test_run_without_proof_mode() {
+    let manifest = Manifest::from_tasks(vec![TaskSource::CairoPie {
+        path: example_path("noop.zip"),
+        use_poseidon: false,
+    }]);
+
+    let result = BootloaderRunner::from_manifest(&manifest)
+        .unwrap()
+        .with_proof_mode(false)
+        .run()
+        .unwrap();
+
+    assert!(result.execution_resources.n_steps > 0);
+    let tasks = result.bootloader_output().unwrap().tasks;
+    assert_eq!(tasks.len(), 1);
+    assert!(matches!(&tasks[0], TaskOutput::Plain { .. }));
+}
+
+#[rstest]
+#[case::program("mod_builtins.json")]
+#[case::cairo_pie("mod_builtins.zip")]
+fn test_run_mod_builtins(#[case] name: &str) {
+    // (30 + 80) * 80 mod 97, computed with the add_mod and mul_mod builtins passed to the task
+    // by the bootloader.
+    assert_eq!(run_task(test_program_path(name)), [Felt252::from(70)]);
+}
+
+#[rstest]
+#[case::program("ecdsa.json")]
+#[case::cairo_pie("ecdsa.zip")]
+fn test_run_ecdsa(#[case] name: &str) {
+    // The signature checked by the task is added to the ECDSA builtin of the bootloader, from the
+    // additional data of the builtin for the PIE, and checked by the VM.
+    let message =
+        Felt252::from_hex("0x5bb9440e27889a364bcb678b1f679ecd1347acdedcbf36e83494f857cc58026")
+            .unwrap();
+    assert_eq!(run_task(test_program_path(name)), [message]);
+}
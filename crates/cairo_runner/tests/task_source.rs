@@ -0,0 +1,154 @@
+mod common;
+
+use std::path::PathBuf;
+
+use cairo_runner::task::{
+    make_bootloader_tasks, BootloaderTaskError, EntrypointSelector, ProgramKind, TaskSource,
+};
+
+use common::example_path;
+
+#[test]
+fn test_detect_cairo_pie() {
+    let kind = ProgramKind::detect(&example_path("noop.zip")).unwrap();
+    assert_eq!(kind, ProgramKind::CairoPie);
+}
+
+#[test]
+fn test_detect_cairo0_program() {
+    let kind = ProgramKind::detect(&example_path("fibonacci_with_hint.json")).unwrap();
+    assert_eq!(kind, ProgramKind::Cairo0);
+}
+
+#[test]
+fn test_detect_unknown_format() {
+    let result = ProgramKind::detect(&example_path("fibonacci_input.json"));
+    assert!(matches!(
+        result,
+        Err(BootloaderTaskError::UnknownFormat(_, _))
+    ));
+}
+
+#[test]
+fn test_detect_missing_file() {
+    let result = ProgramKind::detect(&example_path("missing.json"));
+    assert!(matches!(result, Err(BootloaderTaskError::Read(_, _))));
+}
+
+#[test]
+fn test_detect_task_source() {
+    let source: TaskSource = format!(
+        "{},{}",
+        example_path("fibonacci_with_hint.json").display(),
+        example_path("fibonacci_input.json").display()
+    )
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        source.detect().unwrap(),
+        TaskSource::Cairo0 {
+            path: example_path("fibonacci_with_hint.json"),
+            program_input: Some(example_path("fibonacci_input.json")),
+            use_poseidon: false,
+        }
+    );
+}
+
+#[test]
+fn test_pie_does_not_take_input_file() {
+    let source: TaskSource = format!(
+        "{},{}",
+        example_path("noop.zip").display(),
+        example_path("fibonacci_input.json").display()
+    )
+    .parse()
+    .unwrap();
+
+    assert!(matches!(
+        source.detect(),
+        Err(BootloaderTaskError::UnexpectedInputFile(_))
+    ));
+}
+
+#[test]
+fn test_parse_entrypoint() {
+    let source: TaskSource = "executable:fib.executable.json@1,args.json"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        source,
+        TaskSource::Executable {
+            path: PathBuf::from("fib.executable.json"),
+            args_file: Some(PathBuf::from("args.json")),
+            entrypoint: EntrypointSelector::Index(1),
+            use_poseidon: false,
+        }
+    );
+
+    let source: TaskSource = "fib.executable.json@standalone".parse().unwrap();
+    assert_eq!(
+        source,
+        TaskSource::Auto {
+            path: PathBuf::from("fib.executable.json"),
+            input_file: None,
+            entrypoint: Some(EntrypointSelector::Standalone),
+            use_poseidon: false,
+        }
+    );
+}
+
+#[test]
+fn test_parse_path_with_at() {
+    let source: TaskSource = "tasks@v2/fib.json".parse().unwrap();
+    assert_eq!(source.path(), PathBuf::from("tasks@v2/fib.json"));
+}
+
+#[test]
+fn test_parse_entrypoint_of_cairo0_program() {
+    let result = "cairo0:fib.json@1".parse::<TaskSource>();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_entrypoint_of_detected_cairo0_program() {
+    let source = TaskSource::Auto {
+        path: example_path("fibonacci_with_hint.json"),
+        input_file: None,
+        entrypoint: Some(EntrypointSelector::Index(0)),
+        use_poseidon: false,
+    };
+    assert!(matches!(
+        source.detect(),
+        Err(BootloaderTaskError::UnexpectedEntrypoint(_))
+    ));
+}
+
+#[test]
+fn test_make_bootloader_tasks_reports_task_index() {
+    let sources = [
+        TaskSource::CairoPie {
+            path: example_path("noop.zip"),
+            use_poseidon: false,
+        },
+        TaskSource::Cairo0 {
+            path: example_path("fibonacci_with_hint.json"),
+            program_input: Some(example_path("missing.json")),
+            use_poseidon: false,
+        },
+    ];
+
+    let result = make_bootloader_tasks(&sources);
+
+    let Err(BootloaderTaskError::Task {
+        index,
+        path,
+        source,
+    }) = result
+    else {
+        panic!("Expected a task error");
+    };
+    assert_eq!(index, 1);
+    assert_eq!(path, example_path("fibonacci_with_hint.json"));
+    assert!(matches!(*source, BootloaderTaskError::ProgramInput(_, _)));
+}
@@ -0,0 +1,73 @@
+"""
+Prints the Blake2s hashes of programs as computed by the bootloader of this repository, keyed by
+path, for the Blake2s cases of crates/cairo_runner/tests/program_hash.rs.
+
+compute_program_hash follows `compute_program_hash` in bootloader/execute_task.cairo, which hashes
+the program data chain with `encode_felt252_data_and_calc_224_bit_blake_hash` of cairo-lang's
+starkware/cairo/common/cairo_blake2s/blake2s.cairo. Blake2s is the one of Python's hashlib, so
+that the script runs without cairo-lang and its dependencies.
+
+Compiled Cairo 0 programs, Cairo PIEs (.zip) and Cairo 1 executables (.executable.json) are
+supported, loaded as in scripts/program_hashes.py.
+
+Usage: python scripts/blake2s_program_hashes.py <path>...
+"""
+
+import hashlib
+import json
+import sys
+import zipfile
+
+
+def load_program(path):
+    """
+    Returns the builtins, main offset and data of a program.
+    """
+    if path.endswith(".zip"):
+        with zipfile.ZipFile(path) as pie:
+            program = json.loads(pie.read("metadata.json"))["program"]
+        return program["builtins"], program["main"], [int(word) for word in program["data"]]
+    with open(path) as f:
+        program_json = json.load(f)
+    if path.endswith(".executable.json"):
+        entrypoint = next(e for e in program_json["entrypoints"] if e["kind"] == "Bootloader")
+        data = [int(word, 16) for word in program_json["program"]["bytecode"]]
+        return entrypoint["builtins"], entrypoint["offset"], data
+    main = program_json["identifiers"][program_json["main_scope"] + ".main"]["pc"]
+    return program_json["builtins"], main, [int(word, 16) for word in program_json["data"]]
+
+
+def encode_felt252_to_u32s(data):
+    """
+    Small felts (below 2**63) are encoded as two 32-bit words, the others as eight 32-bit words
+    with the most significant bit set, all in big-endian order.
+    """
+    words = []
+    for felt in data:
+        if felt < 2**63:
+            words += [(felt >> 32) & 0xFFFFFFFF, felt & 0xFFFFFFFF]
+        else:
+            felt += 2**255
+            words += [(felt >> (32 * i)) & 0xFFFFFFFF for i in reversed(range(8))]
+    return words
+
+
+def encode_felt252_data_and_calc_224_bit_blake_hash(data):
+    message = b"".join(word.to_bytes(4, "little") for word in encode_felt252_to_u32s(data))
+    digest = hashlib.blake2s(message).digest()
+    return int.from_bytes(digest[:28], "little")
+
+
+def compute_program_hash(builtins, main, data, bootloader_version=0):
+    builtin_list = [int.from_bytes(builtin.encode("ascii"), "big") for builtin in builtins]
+    data_chain = [bootloader_version, main, len(builtins)] + builtin_list + data
+    return encode_felt252_data_and_calc_224_bit_blake_hash(data_chain)
+
+
+def main():
+    hashes = {path: hex(compute_program_hash(*load_program(path))) for path in sys.argv[1:]}
+    json.dump(hashes, sys.stdout, indent=4)
+
+
+if __name__ == "__main__":
+    main()
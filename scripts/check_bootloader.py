@@ -0,0 +1,32 @@
+"""
+Checks that a compiled bootloader matches a fresh compilation of its sources, ignoring the debug
+info, which holds the absolute paths of the source files.
+
+Usage: python scripts/check_bootloader.py <committed.json> <compiled.json>
+"""
+
+import json
+import sys
+
+
+def main():
+    committed_path, compiled_path = sys.argv[1:]
+    with open(committed_path) as f:
+        committed = json.load(f)
+    with open(compiled_path) as f:
+        compiled = json.load(f)
+
+    stale_fields = [
+        field
+        for field in compiled
+        if field != "debug_info" and committed.get(field) != compiled[field]
+    ]
+    if stale_fields:
+        sys.exit(
+            f"{committed_path} differs from its sources in {', '.join(stale_fields)}, "
+            "run `make compile` and commit it."
+        )
+
+
+if __name__ == "__main__":
+    main()
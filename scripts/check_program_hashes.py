@@ -0,0 +1,44 @@
+"""
+Checks that the Pedersen and Poseidon hashes computed by cairo-lang for programs are the expected
+hashes of a test file. Compiled Cairo 0 programs are also hashed with `cairo-hash-program`, as the
+`hash-program` subcommand hashes them.
+
+Usage: python scripts/check_program_hashes.py <test file> <path>...
+"""
+
+import subprocess
+import sys
+
+from program_hashes import program_hashes
+
+
+def cairo_hash_program(path, use_poseidon):
+    args = ["cairo-hash-program", "--program", path] + (["--use_poseidon"] if use_poseidon else [])
+    return hex(int(subprocess.check_output(args, text=True).strip(), 16))
+
+
+def main():
+    test_path, *paths = sys.argv[1:]
+    with open(test_path) as f:
+        test_source = f.read()
+
+    hashes = program_hashes(paths)
+    for path in paths:
+        if path.endswith(".zip") or path.endswith(".executable.json"):
+            continue
+        for hash_function, use_poseidon in [("pedersen", False), ("poseidon", True)]:
+            program_hash = cairo_hash_program(path, use_poseidon)
+            assert hashes[path][hash_function] == program_hash, (path, hash_function)
+
+    missing = [
+        f"{path} ({hash_function}): {program_hash}"
+        for path, path_hashes in hashes.items()
+        for hash_function, program_hash in path_hashes.items()
+        if f'"{program_hash}"' not in test_source
+    ]
+    if missing:
+        sys.exit(f"Hashes computed by cairo-lang missing from {test_path}:\n" + "\n".join(missing))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,171 @@
+"""
+Prints the expected output roots and facts of the golden tests of
+crates/cairo_bootloader_hints/src/facts.rs.
+
+generate_output_root and generate_program_fact follow the functions of the same name in
+cairo-lang's starkware/cairo/bootloaders/compute_fact.py, with keccak_ints over a pure-Python
+Keccak-256 so that the script runs without cairo-lang and its dependencies.
+
+Usage: python scripts/fact_vectors.py
+"""
+
+import dataclasses
+from typing import List
+
+# Keccak-256, as used by Ethereum (padding 0x01, not the 0x06 of SHA3-256).
+ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+ROTATIONS = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+MASK = (1 << 64) - 1
+RATE = 136
+
+
+def rol(value, shift):
+    return ((value << shift) | (value >> (64 - shift))) & MASK if shift else value
+
+
+def keccak_f(state):
+    for round_constant in ROUND_CONSTANTS:
+        c = [state[x][0] ^ state[x][1] ^ state[x][2] ^ state[x][3] ^ state[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ rol(c[(x + 1) % 5], 1) for x in range(5)]
+        state = [[state[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = rol(state[x][y], ROTATIONS[x][y])
+        state = [
+            [b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y]) for y in range(5)]
+            for x in range(5)
+        ]
+        state[0][0] ^= round_constant
+    return state
+
+
+def keccak256(data: bytes) -> bytes:
+    padded = bytearray(data) + b"\x01" + b"\x00" * ((-len(data) - 1) % RATE)
+    padded[-1] |= 0x80
+    state = [[0] * 5 for _ in range(5)]
+    for block_start in range(0, len(padded), RATE):
+        block = padded[block_start : block_start + RATE]
+        for i in range(RATE // 8):
+            x, y = i % 5, i // 5
+            state[x][y] ^= int.from_bytes(block[8 * i : 8 * i + 8], "little")
+        state = keccak_f(state)
+    return b"".join(state[i % 5][i // 5].to_bytes(8, "little") for i in range(4))
+
+
+def keccak_ints(values: List[int]) -> str:
+    """
+    Computes the keccak of a list of ints.
+    This function is compatible with
+      Web3.solidityKeccak(['uint256[]'], [values]).hex()
+    """
+    return "0x" + keccak256(b"".join(value.to_bytes(32, "big") for value in values)).hex()
+
+
+@dataclasses.dataclass
+class FactTopology:
+    tree_structure: List[int]
+    page_sizes: List[int]
+
+
+@dataclasses.dataclass
+class FactNode:
+    node_hash: int
+    end_offset: int
+    size: int
+    children: List["FactNode"]
+
+
+def generate_output_root(program_output: List[int], fact_topology: FactTopology) -> FactNode:
+    tree_structure = fact_topology.tree_structure
+    page_sizes = fact_topology.page_sizes
+    # The first element of each node_stack entry is the node hash, and the second is the
+    # offset of the page end.
+    node_stack: List[FactNode] = []
+    # The offset within the output of the current page.
+    offset = 0
+    # The index of the current page.
+    page_index = 0
+    assert len(tree_structure) % 2 == 0, "Tree structure should be a list of pairs."
+    for n_pages, n_nodes in zip(tree_structure[::2], tree_structure[1::2]):
+        # Push n_pages to the stack.
+        for _ in range(n_pages):
+            page_size = page_sizes[page_index]
+            page_index += 1
+            end_offset = offset + page_size
+            node_stack.append(
+                FactNode(
+                    node_hash=int(keccak_ints(program_output[offset:end_offset]), 16),
+                    end_offset=end_offset,
+                    size=page_size,
+                    children=[],
+                )
+            )
+            offset = end_offset
+
+        if n_nodes == 0:
+            continue
+
+        # Create a parent node to the last n_nodes in the head of the stack.
+        assert 0 < n_nodes <= len(node_stack), "Invalid value of n_nodes."
+        child_nodes = node_stack[-n_nodes:]
+        del node_stack[-n_nodes:]
+        node_data = [x for node in child_nodes for x in [node.node_hash, node.end_offset]]
+        node_stack.append(
+            FactNode(
+                # Add one to the hash to distinguish it from the hash of a page.
+                node_hash=int(keccak_ints(node_data), 16) + 1,
+                end_offset=child_nodes[-1].end_offset,
+                size=sum(node.size for node in child_nodes),
+                children=child_nodes,
+            )
+        )
+
+    assert len(node_stack) == 1, "Fact tree should have a single root node."
+    assert page_index == len(page_sizes), "Not all pages were used."
+    assert offset == len(program_output), "Not all output was used."
+    return node_stack[0]
+
+
+def generate_program_fact(
+    program_hash: int, program_output: List[int], fact_topology: FactTopology
+) -> str:
+    output_root = generate_output_root(program_output, fact_topology)
+    return keccak_ints([program_hash, output_root.node_hash])
+
+
+def print_fact(name: str, program_hash: int, output: List[int], fact_topology: FactTopology):
+    output_root = generate_output_root(output, fact_topology).node_hash
+    fact = generate_program_fact(program_hash, output, fact_topology)
+    print(f"{name}:\n    output_root: {output_root:#066x}\n    fact: {fact}")
+
+
+def main():
+    # test_compute_fact_golden.
+    print_fact("two_pages", 0x1234, [1, 2, 3, 4, 5], FactTopology([2, 2], [2, 3]))
+    print_fact(
+        "nested_nodes", 0x1234, [1, 2, 3, 4, 5, 6], FactTopology([2, 0, 2, 2, 0, 3], [1, 2, 2, 1])
+    )
+
+    # test_compute_bootloader_facts_golden.
+    output = [2, 7, 0xAA, 1, 2, 3, 4, 5, 3, 0xBB, 6]
+    print_fact("bootloader", 0xB007, output, FactTopology([1, 0], [len(output)]))
+    print_fact("task 0", 0xAA, output[3:8], FactTopology([2, 2], [2, 3]))
+    print_fact("task 1", 0xBB, output[10:], FactTopology([1, 0], [1]))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,52 @@
+"""
+Prints the Pedersen and Poseidon hashes of programs as computed by cairo-lang, keyed by path.
+
+Compiled Cairo 0 programs, Cairo PIEs (.zip) and Cairo 1 executables (.executable.json) are
+supported. Executables are hashed as the program of their first bootloader entrypoint, as the
+bootloader loads them.
+
+Usage: python scripts/program_hashes.py <path>...
+"""
+
+import json
+import sys
+
+from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+from starkware.cairo.lang.cairo_constants import DEFAULT_PRIME
+from starkware.cairo.lang.compiler.program import Program, StrippedProgram
+from starkware.cairo.lang.vm.cairo_pie import CairoPie
+
+
+def load_program(path):
+    if path.endswith(".zip"):
+        return CairoPie.from_file(path).program
+    with open(path) as f:
+        program_json = json.load(f)
+    if path.endswith(".executable.json"):
+        entrypoint = next(e for e in program_json["entrypoints"] if e["kind"] == "Bootloader")
+        return StrippedProgram(
+            prime=DEFAULT_PRIME,
+            data=[int(word, 16) for word in program_json["program"]["bytecode"]],
+            builtins=entrypoint["builtins"],
+            main=entrypoint["offset"],
+        )
+    return Program.Schema().load(program_json)
+
+
+def program_hashes(paths):
+    hashes = {}
+    for path in paths:
+        program = load_program(path)
+        hashes[path] = {
+            "pedersen": hex(compute_program_hash_chain(program, use_poseidon=False)),
+            "poseidon": hex(compute_program_hash_chain(program, use_poseidon=True)),
+        }
+    return hashes
+
+
+def main():
+    json.dump(program_hashes(sys.argv[1:]), sys.stdout, indent=4)
+
+
+if __name__ == "__main__":
+    main()